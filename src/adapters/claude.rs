@@ -0,0 +1,83 @@
+use super::{Adapter, AdapterContext, Capabilities};
+use crate::commands::{pull, push};
+use crate::config::DozoConfig;
+use crate::output::Output;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+const NAME: &str = "claude";
+
+/// Claude Code: `CLAUDE.md` and `.claude/commands/`.
+pub struct ClaudeAdapter;
+
+/// `[adapters.claude]` options in `dozo.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ClaudeOptions {
+    /// Combined memory file.
+    pub output: String,
+    /// Directory the config directory's commands are copied to.
+    pub commands_dir: String,
+    /// Template for the memory file, relative to the config directory.
+    /// Defaults to `<templates_dir>/CLAUDE.md` when that file exists.
+    pub template: Option<String>,
+}
+
+impl Default for ClaudeOptions {
+    fn default() -> Self {
+        Self {
+            output: "CLAUDE.md".to_string(),
+            commands_dir: ".claude/commands".to_string(),
+            template: None,
+        }
+    }
+}
+
+impl ClaudeOptions {
+    pub fn from_config(config: &DozoConfig) -> Result<Self> {
+        config.adapter_options(NAME)
+    }
+}
+
+#[async_trait]
+impl Adapter for ClaudeAdapter {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            push: true,
+            pull: true,
+        }
+    }
+
+    fn validate_options(&self, config: &DozoConfig) -> Result<()> {
+        ClaudeOptions::from_config(config).map(|_| ())
+    }
+
+    fn detect(&self, ctx: &AdapterContext) -> bool {
+        let options = ClaudeOptions::from_config(&ctx.config).unwrap_or_default();
+        ctx.project_path(&options.output).exists()
+            || ctx.project_path(&options.commands_dir).exists()
+    }
+
+    fn render(&self, ctx: &AdapterContext) -> Result<Option<Vec<Output>>> {
+        push::claude::render(ctx).map(Some)
+    }
+
+    fn managed_dirs(&self, ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        let options = ClaudeOptions::from_config(&ctx.config)?;
+        Ok(vec![PathBuf::from(options.commands_dir)])
+    }
+
+    fn imported_files(&self, ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        pull::claude::imported_files(ctx)
+    }
+
+    async fn pull(&self, ctx: &AdapterContext) -> Result<()> {
+        pull::claude::pull_from(ctx)
+    }
+}
@@ -0,0 +1,84 @@
+use super::{Adapter, AdapterContext, Capabilities};
+use crate::commands::{pull, push};
+use crate::config::DozoConfig;
+use crate::output::Output;
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+const NAME: &str = "cursor";
+
+/// Cursor: `.cursor/rules/*.mdc` and the legacy `.cursorrules` file.
+pub struct CursorAdapter;
+
+/// `[adapters.cursor]` options in `dozo.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct CursorOptions {
+    /// Directory of `.mdc` rules.
+    pub rules_dir: String,
+    /// Legacy single-file rules.
+    pub cursorrules: String,
+}
+
+impl Default for CursorOptions {
+    fn default() -> Self {
+        Self {
+            rules_dir: ".cursor/rules".to_string(),
+            cursorrules: ".cursorrules".to_string(),
+        }
+    }
+}
+
+impl CursorOptions {
+    pub fn from_config(config: &DozoConfig) -> Result<Self> {
+        config.adapter_options(NAME)
+    }
+}
+
+#[async_trait]
+impl Adapter for CursorAdapter {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            push: true,
+            pull: true,
+        }
+    }
+
+    fn validate_options(&self, config: &DozoConfig) -> Result<()> {
+        CursorOptions::from_config(config).map(|_| ())
+    }
+
+    fn detect(&self, ctx: &AdapterContext) -> bool {
+        let options = CursorOptions::from_config(&ctx.config).unwrap_or_default();
+        ctx.project_path(&options.rules_dir).exists()
+            || ctx.project_path(&options.cursorrules).exists()
+    }
+
+    fn render(&self, ctx: &AdapterContext) -> Result<Option<Vec<Output>>> {
+        push::cursor::render(ctx).map(Some)
+    }
+
+    fn is_affected_by(&self, ctx: &AdapterContext, path: &Path) -> bool {
+        // Commands and templates are Claude-only.
+        !ctx.config.is_excluded(path)
+    }
+
+    fn managed_dirs(&self, ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        let options = CursorOptions::from_config(&ctx.config)?;
+        Ok(vec![PathBuf::from(options.rules_dir)])
+    }
+
+    fn imported_files(&self, ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        pull::cursor::imported_files(ctx)
+    }
+
+    async fn pull(&self, ctx: &AdapterContext) -> Result<()> {
+        pull::cursor::pull_from(ctx)
+    }
+}
@@ -0,0 +1,96 @@
+use super::{Adapter, AdapterContext, Capabilities};
+use crate::commands::{pull, push};
+use crate::config::DozoConfig;
+use crate::devin::select::{SelectOptions, Selection};
+use crate::devin::{DevinClient, API_KEY_ENV};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+const NAME: &str = "devin";
+
+/// Devin: knowledge items stored behind the Devin API.
+#[derive(Debug, Default)]
+pub struct DevinAdapter {
+    api_key: Option<String>,
+}
+
+/// `[adapters.devin]` options in `dozo.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct DevinOptions {
+    /// API base URL. `DEVIN_API_BASE_URL` takes precedence.
+    pub base_url: Option<String>,
+    /// Seconds to wait for each API request.
+    pub timeout_secs: u64,
+    /// Which knowledge items a pull imports.
+    pub select: SelectOptions,
+}
+
+impl Default for DevinOptions {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            timeout_secs: 30,
+            select: SelectOptions::default(),
+        }
+    }
+}
+
+impl DevinOptions {
+    pub fn from_config(config: &DozoConfig) -> Result<Self> {
+        config.adapter_options(NAME)
+    }
+}
+
+impl DevinAdapter {
+    /// Uses `api_key` instead of `DEVIN_API_KEY`.
+    pub fn with_api_key(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: Some(api_key.into()),
+        }
+    }
+
+    fn client(&self, ctx: &AdapterContext) -> Result<DevinClient> {
+        let options = DevinOptions::from_config(&ctx.config)?;
+        Ok(match &self.api_key {
+            Some(api_key) => DevinClient::with_options(api_key.as_str(), &options),
+            None => DevinClient::from_options(&options)?,
+        })
+    }
+}
+
+#[async_trait]
+impl Adapter for DevinAdapter {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            push: true,
+            pull: true,
+        }
+    }
+
+    fn validate_options(&self, config: &DozoConfig) -> Result<()> {
+        let options = DevinOptions::from_config(config)?;
+        Selection::new(&options.select).map(|_| ())
+    }
+
+    fn detect(&self, _ctx: &AdapterContext) -> bool {
+        self.api_key.is_some() || std::env::var_os(API_KEY_ENV).is_some()
+    }
+
+    fn is_remote(&self) -> bool {
+        true
+    }
+
+    async fn push(&self, ctx: &AdapterContext) -> Result<Vec<String>> {
+        push::devin::push_to(ctx, &self.client(ctx)?).await
+    }
+
+    async fn pull(&self, ctx: &AdapterContext) -> Result<()> {
+        pull::devin::pull_from(ctx, &self.client(ctx)?).await
+    }
+}
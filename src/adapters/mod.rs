@@ -0,0 +1,370 @@
+//! Pluggable tool adapters.
+//!
+//! Every coding agent dozo can sync with is described by an [`Adapter`]. The
+//! [`AdapterRegistry`] is the single list of known tools: the CLI validates
+//! tool names against it and `--target all` expands to every registered
+//! adapter, so adding a tool only means registering another adapter.
+
+mod claude;
+mod cursor;
+mod devin;
+
+pub use claude::{ClaudeAdapter, ClaudeOptions};
+pub use cursor::{CursorAdapter, CursorOptions};
+pub use devin::{DevinAdapter, DevinOptions};
+
+use crate::config::DozoConfig;
+use crate::effects::Effects;
+use crate::error::DozoError;
+use crate::output::Output;
+use crate::report::Reporter;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Target name that expands to every registered adapter.
+pub const ALL_TARGETS: &str = "all";
+
+/// What an adapter is able to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Can generate the tool's configuration from the config directory.
+    pub push: bool,
+    /// Can import the tool's configuration into the config directory.
+    pub pull: bool,
+}
+
+/// Paths and flags shared by every adapter invocation.
+#[derive(Debug, Clone)]
+pub struct AdapterContext {
+    /// The dozo configuration directory (`.agentic-coding` by default).
+    pub config_dir: PathBuf,
+    /// Directory that tool outputs are resolved against. An empty path means
+    /// the current directory.
+    pub project_root: PathBuf,
+    /// Settings loaded from `dozo.toml`.
+    pub config: DozoConfig,
+    /// Subdirectory of the config directory that pulled rules are written
+    /// to, so several tools can be imported side by side.
+    pub namespace: Option<String>,
+    pub force: bool,
+    pub verbose: bool,
+    /// Pull every item the tool has, ignoring selection rules.
+    pub select_all: bool,
+    /// Print why each item was pulled or skipped.
+    pub explain: bool,
+    /// Every file write, removal and API call goes through here so dry runs
+    /// can record them instead.
+    pub effects: Arc<Effects>,
+    /// Progress output and the structured report for `--format json`.
+    pub reporter: Arc<Reporter>,
+}
+
+impl AdapterContext {
+    pub fn new(config_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            config_dir: config_dir.into(),
+            project_root: PathBuf::new(),
+            config: DozoConfig::default(),
+            namespace: None,
+            force: false,
+            verbose: false,
+            select_all: false,
+            explain: false,
+            effects: Arc::new(Effects::default()),
+            reporter: Arc::new(Reporter::default()),
+        }
+    }
+
+    pub fn with_project_root(mut self, project_root: impl Into<PathBuf>) -> Self {
+        self.project_root = project_root.into();
+        self
+    }
+
+    pub fn with_config(mut self, config: DozoConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    pub fn with_select_all(mut self, select_all: bool) -> Self {
+        self.select_all = select_all;
+        self
+    }
+
+    pub fn with_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.effects = Arc::new(Effects::new(dry_run));
+        self
+    }
+
+    pub fn with_reporter(mut self, reporter: Arc<Reporter>) -> Self {
+        self.reporter = reporter;
+        self
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.effects.is_dry_run()
+    }
+
+    /// Directory pulled rules are written to.
+    pub fn import_dir(&self) -> PathBuf {
+        match &self.namespace {
+            Some(namespace) => self.config_dir.join(namespace),
+            None => self.config_dir.clone(),
+        }
+    }
+
+    /// Resolves a project-relative path against the project root.
+    pub fn project_path(&self, relative: &str) -> PathBuf {
+        self.project_root.join(relative)
+    }
+}
+
+/// A coding agent that dozo can push configuration to and pull it from.
+#[async_trait]
+pub trait Adapter: Send + Sync {
+    /// Name used on the command line (`--target <name>`, `--from <name>`).
+    fn name(&self) -> &'static str;
+
+    fn capabilities(&self) -> Capabilities;
+
+    /// Validates the adapter's `[adapters.<name>]` table in `dozo.toml`.
+    /// Adapters without options reject any keys.
+    fn validate_options(&self, config: &DozoConfig) -> Result<()> {
+        match config.adapters.get(self.name()) {
+            Some(table) if !table.is_empty() => {
+                let keys: Vec<_> = table.keys().map(String::as_str).collect();
+                anyhow::bail!(
+                    "Invalid [adapters.{}] options: {} takes no options (found: {})",
+                    self.name(),
+                    self.name(),
+                    keys.join(", ")
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns true when the project already has configuration for this tool.
+    fn detect(&self, ctx: &AdapterContext) -> bool;
+
+    /// Returns true for adapters that push to a remote service. They are
+    /// only pushed when selected explicitly, in `targets` or with `--target`.
+    fn is_remote(&self) -> bool {
+        false
+    }
+
+    /// Renders the project files this adapter generates without writing
+    /// anything. dozo writes them, records them in the manifest and prunes
+    /// stale ones. Adapters that do not generate project files return `None`.
+    fn render(&self, _ctx: &AdapterContext) -> Result<Option<Vec<Output>>> {
+        Ok(None)
+    }
+
+    /// Returns true when a change to `path`, relative to the config
+    /// directory, can change what this adapter renders. `dozo watch` only
+    /// re-pushes affected adapters.
+    fn is_affected_by(&self, _ctx: &AdapterContext, _path: &Path) -> bool {
+        true
+    }
+
+    /// Project-relative directories that hold this tool's generated files.
+    /// `dozo status` reports files found there that dozo did not generate.
+    fn managed_dirs(&self, _ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// Project-relative files `pull` imports from. `dozo init` records them
+    /// in the manifest, so the next push replaces them instead of adding
+    /// copies next to them.
+    fn imported_files(&self, _ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// Pushes configuration for adapters that do not render project files,
+    /// such as API-backed tools, and returns what was updated.
+    async fn push(&self, _ctx: &AdapterContext) -> Result<Vec<String>> {
+        Err(DozoError::Unsupported {
+            tool: self.name().to_string(),
+            operation: "push".to_string(),
+        }
+        .into())
+    }
+
+    /// Imports the tool's configuration into `ctx.config_dir`.
+    async fn pull(&self, ctx: &AdapterContext) -> Result<()>;
+}
+
+/// The set of adapters available to the CLI.
+pub struct AdapterRegistry {
+    adapters: Vec<Box<dyn Adapter>>,
+}
+
+impl AdapterRegistry {
+    /// Creates a registry without any adapters.
+    pub fn empty() -> Self {
+        Self {
+            adapters: Vec::new(),
+        }
+    }
+
+    /// Creates a registry with the built-in Cursor, Claude and Devin adapters.
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::empty();
+        registry.register(CursorAdapter);
+        registry.register(ClaudeAdapter);
+        registry.register(DevinAdapter::default());
+        registry
+    }
+
+    /// Registers an adapter, replacing any existing adapter with the same name.
+    pub fn register(&mut self, adapter: impl Adapter + 'static) -> &mut Self {
+        self.adapters.retain(|a| a.name() != adapter.name());
+        self.adapters.push(Box::new(adapter));
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Adapter> {
+        self.adapters
+            .iter()
+            .find(|a| a.name() == name)
+            .map(|a| a.as_ref())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Adapter> {
+        self.adapters.iter().map(|a| a.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.adapters.iter().map(|a| a.name()).collect()
+    }
+
+    /// Looks up a single adapter by name.
+    pub fn find(&self, name: &str) -> Result<&dyn Adapter, DozoError> {
+        self.get(name).ok_or_else(|| DozoError::UnknownTool {
+            name: name.to_string(),
+            available: self.names().join(", "),
+        })
+    }
+
+    /// Expands a `--target` value into the adapters it refers to.
+    pub fn resolve_target(&self, target: &str) -> Result<Vec<&dyn Adapter>, DozoError> {
+        if target == ALL_TARGETS {
+            return Ok(self.iter().collect());
+        }
+        self.get(target)
+            .map(|a| vec![a])
+            .ok_or_else(|| DozoError::UnknownTool {
+                name: target.to_string(),
+                available: format!("{}, {}", self.names().join(", "), ALL_TARGETS),
+            })
+    }
+}
+
+impl Default for AdapterRegistry {
+    fn default() -> Self {
+        Self::with_builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DummyAdapter;
+
+    #[async_trait]
+    impl Adapter for DummyAdapter {
+        fn name(&self) -> &'static str {
+            "dummy"
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities {
+                push: true,
+                pull: false,
+            }
+        }
+
+        fn detect(&self, _ctx: &AdapterContext) -> bool {
+            false
+        }
+
+        fn render(&self, _ctx: &AdapterContext) -> Result<Option<Vec<Output>>> {
+            Ok(Some(vec![Output::file("dummy.txt", "dummy")]))
+        }
+
+        async fn pull(&self, _ctx: &AdapterContext) -> Result<()> {
+            anyhow::bail!("dummy cannot pull")
+        }
+    }
+
+    #[test]
+    fn test_resolve_target() {
+        let registry = AdapterRegistry::with_builtin();
+        assert!(registry.resolve_target("cursor").is_ok());
+        assert!(registry.resolve_target("claude").is_ok());
+        assert!(registry.resolve_target("devin").is_ok());
+        assert_eq!(registry.resolve_target("all").unwrap().len(), 3);
+
+        match registry.resolve_target("invalid") {
+            Err(e) => assert!(e.to_string().contains("cursor, claude, devin, all")),
+            Ok(_) => panic!("Expected unknown tool error"),
+        }
+    }
+
+    #[test]
+    fn test_find_rejects_all() {
+        let registry = AdapterRegistry::with_builtin();
+        assert!(registry.find("devin").is_ok());
+        assert!(registry.find("all").is_err());
+    }
+
+    #[test]
+    fn test_register_custom_adapter() {
+        let mut registry = AdapterRegistry::with_builtin();
+        registry.register(DummyAdapter);
+
+        assert_eq!(registry.names(), vec!["cursor", "claude", "devin", "dummy"]);
+        assert_eq!(registry.resolve_target("all").unwrap().len(), 4);
+        assert!(!registry.find("dummy").unwrap().capabilities().pull);
+    }
+
+    #[test]
+    fn test_default_validate_options_rejects_keys() {
+        let config = DozoConfig::parse("[adapters.dummy]\nmode = \"fast\"").unwrap();
+        let err = DummyAdapter.validate_options(&config).unwrap_err();
+        assert!(err.to_string().contains("dummy takes no options"));
+
+        assert!(DummyAdapter
+            .validate_options(&DozoConfig::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_register_replaces_same_name() {
+        let mut registry = AdapterRegistry::empty();
+        registry.register(DummyAdapter).register(DummyAdapter);
+        assert_eq!(registry.names(), vec!["dummy"]);
+    }
+}
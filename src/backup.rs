@@ -0,0 +1,317 @@
+//! Snapshots of generated files taken before dozo changes them.
+//!
+//! Every push or clean that changes project files first records the previous
+//! contents of those files, and the manifest, in
+//! `<config_dir>/.dozo-backups/<snapshot>/`. `dozo restore` undoes snapshots
+//! newest first to return the project to an earlier state.
+
+use crate::effects::Effects;
+use crate::manifest::Manifest;
+use crate::sync::TargetPlan;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const BACKUP_DIR_NAME: &str = ".dozo-backups";
+pub const DEFAULT_KEEP_BACKUPS: usize = 10;
+const SNAPSHOT_FILE_NAME: &str = "snapshot.json";
+const FILES_DIR_NAME: &str = "files";
+
+/// One project file as it was when the snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Project-relative path.
+    pub path: String,
+    /// False when the file did not exist yet; restoring removes it.
+    pub existed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// The command that was about to change the files.
+    pub command: String,
+    pub files: Vec<SnapshotFile>,
+    pub manifest: Manifest,
+}
+
+impl Snapshot {
+    pub fn root(config_dir: &Path) -> PathBuf {
+        config_dir.join(BACKUP_DIR_NAME)
+    }
+
+    fn dir(&self, config_dir: &Path) -> PathBuf {
+        Self::root(config_dir).join(&self.id)
+    }
+
+    /// Records `files` (project-relative paths with their current content)
+    /// and the manifest as a new snapshot.
+    pub fn create(
+        config_dir: &Path,
+        command: &str,
+        files: Vec<(String, Option<String>)>,
+        manifest: &Manifest,
+        effects: &Effects,
+    ) -> Result<Self> {
+        let created_at = Utc::now();
+        let base_id = created_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+        let mut id = base_id.clone();
+        let mut suffix = 1;
+        while Self::root(config_dir).join(&id).exists() {
+            id = format!("{}-{}", base_id, suffix);
+            suffix += 1;
+        }
+
+        let mut snapshot = Self {
+            id,
+            created_at,
+            command: command.to_string(),
+            files: Vec::new(),
+            manifest: manifest.clone(),
+        };
+        let files_dir = snapshot.dir(config_dir).join(FILES_DIR_NAME);
+        for (path, content) in files {
+            if let Some(content) = &content {
+                effects.write(&files_dir.join(&path), content)?;
+            }
+            snapshot.files.push(SnapshotFile {
+                path,
+                existed: content.is_some(),
+            });
+        }
+
+        let content = serde_json::to_string_pretty(&snapshot)?;
+        effects.write(
+            &snapshot.dir(config_dir).join(SNAPSHOT_FILE_NAME),
+            &(content + "\n"),
+        )?;
+        Ok(snapshot)
+    }
+
+    /// Every snapshot, oldest first.
+    pub fn list(config_dir: &Path) -> Result<Vec<Self>> {
+        let root = Self::root(config_dir);
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in std::fs::read_dir(&root)
+            .with_context(|| format!("Failed to read {}", root.display()))?
+        {
+            let path = entry?.path().join(SNAPSHOT_FILE_NAME);
+            if !path.exists() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let snapshot: Self = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            snapshots.push(snapshot);
+        }
+        snapshots.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(snapshots)
+    }
+
+    /// Puts the recorded files back, removing those that did not exist.
+    fn restore_files(
+        &self,
+        config_dir: &Path,
+        project_root: &Path,
+        effects: &Effects,
+    ) -> Result<()> {
+        let files_dir = self.dir(config_dir).join(FILES_DIR_NAME);
+        for file in &self.files {
+            let target = project_root.join(&file.path);
+            if file.existed {
+                let backup = files_dir.join(&file.path);
+                let content = std::fs::read_to_string(&backup)
+                    .with_context(|| format!("Failed to read {}", backup.display()))?;
+                effects.write(&target, &content)?;
+            } else if target.exists() {
+                effects.remove_file(&target)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&self, config_dir: &Path, effects: &Effects) -> Result<()> {
+        effects.remove_dir_all(&self.dir(config_dir))
+    }
+}
+
+/// Snapshots the files `plans` are about to change. Returns `None` when
+/// nothing would change, in dry-run mode, or when `keep` is 0.
+pub fn backup_plans(
+    config_dir: &Path,
+    command: &str,
+    plans: &[TargetPlan],
+    manifest: &Manifest,
+    keep: usize,
+    effects: &Effects,
+) -> Result<Option<Snapshot>> {
+    if keep == 0 || effects.is_dry_run() {
+        return Ok(None);
+    }
+
+    let files: Vec<_> = plans
+        .iter()
+        .flat_map(|plan| plan.pending())
+        .map(|change| (change.path.clone(), change.before.clone()))
+        .collect();
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let snapshot = Snapshot::create(config_dir, command, files, manifest, effects)?;
+    prune(config_dir, keep, effects)?;
+    Ok(Some(snapshot))
+}
+
+/// Removes the oldest snapshots so that at most `keep` remain. Returns the
+/// number removed.
+pub fn prune(config_dir: &Path, keep: usize, effects: &Effects) -> Result<usize> {
+    let snapshots = Snapshot::list(config_dir)?;
+    let excess = snapshots.len().saturating_sub(keep);
+    for snapshot in &snapshots[..excess] {
+        snapshot.remove(config_dir, effects)?;
+    }
+    Ok(excess)
+}
+
+/// Returns the project to the state it was in when snapshot `id` was taken
+/// by undoing it and every newer snapshot. The current state is saved as a
+/// new snapshot first, so a restore can itself be undone. Returns the
+/// snapshot the project was restored to.
+pub fn restore(
+    config_dir: &Path,
+    project_root: &Path,
+    id: &str,
+    effects: &Effects,
+) -> Result<Snapshot> {
+    let snapshots = Snapshot::list(config_dir)?;
+    let Some(position) = snapshots.iter().position(|s| s.id == id) else {
+        anyhow::bail!(
+            "No backup snapshot '{}'. Run `dozo restore --list` to see the available snapshots",
+            id
+        );
+    };
+    let undone = &snapshots[position..];
+
+    let mut paths: Vec<&str> = undone
+        .iter()
+        .flat_map(|s| s.files.iter().map(|f| f.path.as_str()))
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+    let mut current = Vec::new();
+    for path in paths {
+        let full_path = project_root.join(path);
+        let content = if full_path.exists() {
+            Some(
+                std::fs::read_to_string(&full_path)
+                    .with_context(|| format!("Failed to read {}", full_path.display()))?,
+            )
+        } else {
+            None
+        };
+        current.push((path.to_string(), content));
+    }
+
+    let manifest = Manifest::load(config_dir)?;
+    Snapshot::create(config_dir, "restore", current, &manifest, effects)?;
+
+    for snapshot in undone.iter().rev() {
+        snapshot.restore_files(config_dir, project_root, effects)?;
+    }
+    undone[0].manifest.save(config_dir, effects)?;
+    Ok(undone[0].clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Output;
+    use crate::sync::{apply_plans, plan_target};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn push(root: &Path, config_dir: &Path, manifest: &mut Manifest, outputs: Vec<Output>) {
+        let effects = Effects::default();
+        let plans = vec![plan_target(root, manifest, "test", outputs, true).unwrap()];
+        backup_plans(config_dir, "push", &plans, manifest, 10, &effects).unwrap();
+        apply_plans(root, &plans, manifest, &effects).unwrap();
+        manifest.save(config_dir, &effects).unwrap();
+    }
+
+    #[test]
+    fn test_restore_undoes_newer_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_dir = root.join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(root.join("CLAUDE.md"), "Hand written").unwrap();
+        let mut manifest = Manifest::default();
+
+        push(
+            root,
+            &config_dir,
+            &mut manifest,
+            vec![Output::file("CLAUDE.md", "First")],
+        );
+        push(
+            root,
+            &config_dir,
+            &mut manifest,
+            vec![
+                Output::file("CLAUDE.md", "Second"),
+                Output::file("rules/a.md", "A"),
+            ],
+        );
+        let snapshots = Snapshot::list(&config_dir).unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        restore(&config_dir, root, &snapshots[1].id, &Effects::default()).unwrap();
+        assert_eq!(fs::read_to_string(root.join("CLAUDE.md")).unwrap(), "First");
+        assert!(!root.join("rules/a.md").exists());
+
+        restore(&config_dir, root, &snapshots[0].id, &Effects::default()).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("CLAUDE.md")).unwrap(),
+            "Hand written"
+        );
+        assert_eq!(Manifest::load(&config_dir).unwrap(), Manifest::default());
+
+        assert!(!root.join("rules/a.md").exists());
+        assert_eq!(Snapshot::list(&config_dir).unwrap().len(), 4);
+
+        // A restore is itself a snapshot and can be undone.
+        let undo = Snapshot::list(&config_dir).unwrap().pop().unwrap();
+        assert_eq!(undo.command, "restore");
+        restore(&config_dir, root, &undo.id, &Effects::default()).unwrap();
+        assert_eq!(fs::read_to_string(root.join("CLAUDE.md")).unwrap(), "First");
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path();
+        let effects = Effects::default();
+        for _ in 0..3 {
+            Snapshot::create(
+                config_dir,
+                "push",
+                Vec::new(),
+                &Manifest::default(),
+                &effects,
+            )
+            .unwrap();
+        }
+        let newest = Snapshot::list(config_dir).unwrap().pop().unwrap();
+
+        assert_eq!(prune(config_dir, 1, &effects).unwrap(), 2);
+        assert_eq!(Snapshot::list(config_dir).unwrap(), vec![newest]);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::config::{DozoConfig, DEFAULT_CONFIG_DIR};
+use crate::report::OutputFormat;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -14,24 +16,170 @@ pub struct Cli {
 
     #[arg(short, long)]
     pub config: Option<String>,
+
+    /// Output format: `json` prints one structured report per command
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub format: OutputFormat,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     Push {
-        #[arg(long, default_value = "all")]
-        target: String,
+        /// Target tool, or `all` [default: `targets` from dozo.toml, or every registered adapter]
+        #[arg(long)]
+        target: Option<String>,
 
         #[arg(short, long)]
         force: bool,
+
+        /// Print the planned writes, deletions and API calls without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Push every target that renders even if others fail, instead of stopping at the first failure and writing nothing
+        #[arg(long)]
+        keep_going: bool,
     },
 
     Pull {
+        /// Source tool to import configuration from
         #[arg(long)]
         from: String,
 
         #[arg(long)]
         merge: bool,
+
+        /// Print the planned writes and API calls without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Import every item, ignoring `[adapters.devin.select]` rules
+        #[arg(long)]
+        all: bool,
+
+        /// Print why each item was imported or skipped
+        #[arg(long)]
+        explain: bool,
+    },
+
+    /// Remove every file dozo generated, keeping hand-written content
+    Clean {
+        /// Only clean files generated for this tool [default: every tool in the manifest]
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Also remove generated files that were edited by hand
+        #[arg(short, long)]
+        force: bool,
+
+        /// Print the planned deletions without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Create the config directory by importing every configured tool
+    Init,
+
+    /// Show what `push` would change and where tools have drifted
+    Status {
+        /// Target tool, or `all` [default: `targets` from dozo.toml, or every registered adapter]
+        #[arg(long)]
+        target: Option<String>,
+    },
+
+    /// Re-push affected targets whenever the config directory changes
+    Watch {
+        /// Target tool, or `all` [default: `targets` from dozo.toml, or every registered adapter]
+        #[arg(long)]
+        target: Option<String>,
+
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Fail when generated files are out of date with the config directory
+    Check {
+        /// Target tool, or `all` [default: `targets` from dozo.toml, or every registered adapter]
+        #[arg(long)]
+        target: Option<String>,
+    },
+
+    /// Roll generated files back to a backup snapshot
+    Restore {
+        /// List the available snapshots
+        #[arg(long, conflicts_with = "snapshot")]
+        list: bool,
+
+        /// Snapshot to restore; it and every later run are undone
+        #[arg(required_unless_present = "list")]
+        snapshot: Option<String>,
+    },
+
+    /// Browse the knowledge in your Devin organization
+    Devin {
+        #[command(subcommand)]
+        command: DevinCommand,
+    },
+
+    /// Show a unified diff of the files `push` would change
+    Diff {
+        /// Target tool, or `all` [default: `targets` from dozo.toml, or every registered adapter]
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Include files `push --force` would overwrite
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+impl Commands {
+    /// Subcommand name, as used in reports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Push { .. } => "push",
+            Commands::Pull { .. } => "pull",
+            Commands::Clean { .. } => "clean",
+            Commands::Init => "init",
+            Commands::Status { .. } => "status",
+            Commands::Watch { .. } => "watch",
+            Commands::Check { .. } => "check",
+            Commands::Restore { .. } => "restore",
+            Commands::Diff { .. } => "diff",
+            Commands::Devin { .. } => "devin",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DevinCommand {
+    /// List knowledge items and whether they are already in the config directory
+    List {
+        /// Only items in this folder or its subfolders, e.g. `Frontend/React`
+        #[arg(long)]
+        folder: Option<String>,
+
+        /// Only items whose name matches this regex
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Only items whose trigger matches this regex
+        #[arg(long)]
+        trigger: Option<String>,
+
+        /// Only items already pulled into the config directory
+        #[arg(long, conflicts_with = "missing")]
+        local: bool,
+
+        /// Only items not yet pulled into the config directory
+        #[arg(long)]
+        missing: bool,
+    },
+
+    /// Print one knowledge item, including its body
+    Show {
+        /// Knowledge ID, as printed by `dozo devin list`
+        id: String,
     },
 }
 
@@ -39,20 +187,14 @@ impl Cli {
     pub fn config_dir(&self) -> String {
         self.config
             .clone()
-            .unwrap_or_else(|| ".agentic-coding".to_string())
+            .unwrap_or_else(|| DEFAULT_CONFIG_DIR.to_string())
     }
-}
 
-pub const AVAILABLE_TOOLS: &[&str] = &["cursor", "claude", "devin", "all"];
-pub fn validate_tool_name(tool: &str) -> Result<(), String> {
-    if AVAILABLE_TOOLS.contains(&tool) {
-        Ok(())
-    } else {
-        Err(format!(
-            "Unknown tool '{}'. Available tools: {}",
-            tool,
-            AVAILABLE_TOOLS.join(", ")
-        ))
+    /// Applies command line overrides to the settings loaded from `dozo.toml`.
+    pub fn apply_overrides(&self, config: &mut DozoConfig) {
+        if let Some(config_dir) = &self.config {
+            config.config_dir = Some(config_dir.clone());
+        }
     }
 }
 
@@ -60,15 +202,6 @@ pub fn validate_tool_name(tool: &str) -> Result<(), String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_validate_tool_name() {
-        assert!(validate_tool_name("cursor").is_ok());
-        assert!(validate_tool_name("claude").is_ok());
-        assert!(validate_tool_name("devin").is_ok());
-        assert!(validate_tool_name("all").is_ok());
-        assert!(validate_tool_name("invalid").is_err());
-    }
-
     #[test]
     fn test_cli_parsing() {
         use clap::Parser;
@@ -76,7 +209,7 @@ mod tests {
         let cli = Cli::try_parse_from(["dozo", "--verbose", "push", "--target", "cursor"]).unwrap();
         assert!(cli.verbose);
         if let Commands::Push { target, .. } = cli.command {
-            assert_eq!(target, "cursor");
+            assert_eq!(target.as_deref(), Some("cursor"));
         } else {
             panic!("Expected Push command");
         }
@@ -88,4 +221,48 @@ mod tests {
             panic!("Expected Pull command");
         }
     }
+
+    #[test]
+    fn test_check_format() {
+        use clap::Parser;
+
+        let cli = Cli::try_parse_from(["dozo", "check", "--format", "github"]).unwrap();
+        assert!(matches!(cli.command, Commands::Check { .. }));
+        assert_eq!(cli.format, OutputFormat::Github);
+        assert!(Cli::try_parse_from(["dozo", "check", "--format", "xml"]).is_err());
+
+        // The format is global and can come before the subcommand.
+        let cli = Cli::try_parse_from(["dozo", "--format", "json", "push"]).unwrap();
+        assert_eq!(cli.format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_restore_arguments() {
+        use clap::Parser;
+
+        assert!(Cli::try_parse_from(["dozo", "restore", "--list"]).is_ok());
+        assert!(Cli::try_parse_from(["dozo", "restore"]).is_err());
+        assert!(
+            Cli::try_parse_from(["dozo", "restore", "--list", "20261017T120000.000Z"]).is_err()
+        );
+        let cli = Cli::try_parse_from(["dozo", "restore", "20261017T120000.000Z"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Restore { snapshot: Some(ref id), .. } if id == "20261017T120000.000Z"
+        ));
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        use clap::Parser;
+
+        let mut config = DozoConfig::parse("config_dir = \"from-file\"").unwrap();
+        let cli = Cli::try_parse_from(["dozo", "push"]).unwrap();
+        cli.apply_overrides(&mut config);
+        assert_eq!(config.config_dir(), "from-file");
+
+        let cli = Cli::try_parse_from(["dozo", "--config", "from-cli", "push"]).unwrap();
+        cli.apply_overrides(&mut config);
+        assert_eq!(config.config_dir(), "from-cli");
+    }
 }
@@ -1,17 +1,165 @@
+pub mod check;
+pub mod clean;
+pub mod devin;
+pub mod diff;
+pub mod init;
 pub mod pull;
 pub mod push;
+pub mod restore;
+pub mod status;
 pub mod utils;
+pub mod watch;
 
+use crate::adapters::AdapterRegistry;
 use crate::cli::Commands;
+use crate::config::DozoConfig;
+use crate::error::DozoError;
+use crate::report::{OutputFormat, Reporter};
 use anyhow::Result;
+use std::sync::Arc;
 
-pub async fn execute_command(command: Commands, config_dir: &str, verbose: bool) -> Result<()> {
-    match command {
-        Commands::Push { target, force } => {
-            push::push_command(config_dir, &target, force, verbose).await
+/// Exit code when a command fails, or when every target failed.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code when some targets were pushed and others failed.
+pub const EXIT_PARTIAL_FAILURE: i32 = 3;
+
+/// Maps an error returned by [`execute_command`] to the process exit code.
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    match DozoError::find(error) {
+        Some(DozoError::TargetsFailed { succeeded, .. }) if *succeeded > 0 => EXIT_PARTIAL_FAILURE,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Runs a command. With `--format json`, its report is printed when it
+/// finishes, whether it succeeded or not.
+///
+/// Embedding dozo with an in-house adapter:
+///
+/// ```no_run
+/// # use async_trait::async_trait;
+/// # use dozo::adapters::{Adapter, AdapterContext, Capabilities};
+/// # struct MyInHouseAdapter;
+/// # #[async_trait]
+/// # impl Adapter for MyInHouseAdapter {
+/// #     fn name(&self) -> &'static str { "in-house" }
+/// #     fn capabilities(&self) -> Capabilities { Capabilities { push: true, pull: false } }
+/// #     fn detect(&self, _ctx: &AdapterContext) -> bool { false }
+/// #     async fn pull(&self, _ctx: &AdapterContext) -> anyhow::Result<()> { Ok(()) }
+/// # }
+/// # async fn run() -> anyhow::Result<()> {
+/// use clap::Parser;
+/// use dozo::adapters::AdapterRegistry;
+/// use dozo::cli::Cli;
+/// use dozo::config::DozoConfig;
+/// use std::path::Path;
+///
+/// let mut registry = AdapterRegistry::with_builtin();
+/// registry.register(MyInHouseAdapter);
+///
+/// let cli = Cli::parse();
+/// let mut config = DozoConfig::load(Path::new(""))?;
+/// config.validate(&registry)?;
+/// cli.apply_overrides(&mut config);
+/// dozo::commands::execute_command(&registry, &config, cli.command, cli.format, cli.verbose)
+///     .await
+/// # }
+/// ```
+pub async fn execute_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    command: Commands,
+    format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let reporter = Arc::new(Reporter::new(format, command.name()));
+    let r = &reporter;
+    let result = match command {
+        Commands::Push {
+            target,
+            force,
+            dry_run,
+            keep_going,
+        } => {
+            push::push_command(
+                registry,
+                config,
+                target.as_deref(),
+                force,
+                dry_run,
+                keep_going,
+                verbose,
+                r,
+            )
+            .await
+        }
+        Commands::Pull {
+            from,
+            merge,
+            dry_run,
+            all,
+            explain,
+        } => {
+            pull::pull_command(
+                registry, config, &from, merge, dry_run, all, explain, verbose, r,
+            )
+            .await
+        }
+        Commands::Status { target } => {
+            status::status_command(registry, config, target.as_deref(), verbose, r).await
+        }
+        Commands::Check { target } => {
+            check::check_command(registry, config, target.as_deref(), verbose, r).await
         }
-        Commands::Pull { from, merge } => {
-            pull::pull_command(config_dir, &from, merge, verbose).await
+        Commands::Clean {
+            target,
+            force,
+            dry_run,
+        } => {
+            clean::clean_command(
+                registry,
+                config,
+                target.as_deref(),
+                force,
+                dry_run,
+                verbose,
+                r,
+            )
+            .await
         }
+        Commands::Init => init::init_command(registry, config, verbose, r).await,
+        Commands::Watch { target, force } => {
+            watch::watch_command(registry, config, target.as_deref(), force, verbose, r).await
+        }
+        Commands::Restore { snapshot, .. } => {
+            restore::restore_command(config, snapshot.as_deref(), verbose, r).await
+        }
+        Commands::Devin { command } => devin::devin_command(config, command, verbose, r).await,
+        Commands::Diff { target, force } => {
+            diff::diff_command(registry, config, target.as_deref(), force, verbose, r).await
+        }
+    };
+
+    reporter.finish(result.as_ref().err())?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        let partial = DozoError::TargetsFailed {
+            succeeded: 1,
+            failed: 1,
+        };
+        let total = DozoError::TargetsFailed {
+            succeeded: 0,
+            failed: 2,
+        };
+        assert_eq!(exit_code(&partial.into()), EXIT_PARTIAL_FAILURE);
+        assert_eq!(exit_code(&total.into()), EXIT_FAILURE);
+        assert_eq!(exit_code(&anyhow::anyhow!("boom")), EXIT_FAILURE);
     }
 }
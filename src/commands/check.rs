@@ -0,0 +1,177 @@
+use crate::adapters::{AdapterContext, AdapterRegistry};
+use crate::commands::push::select_adapters;
+use crate::commands::status::{target_status, TargetStatus};
+use crate::commands::utils::ensure_config_exists;
+use crate::config::DozoConfig;
+use crate::error::DozoError;
+use crate::manifest::Manifest;
+use crate::report::Reporter;
+use crate::sync::ChangeKind;
+use anyhow::Result;
+use serde::Serialize;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Problem {
+    /// A generated file does not exist yet.
+    Missing,
+    /// A generated file differs from what push would write.
+    Outdated,
+    /// A generated file is no longer rendered and should be removed.
+    Stale,
+    /// A generated file was edited directly in the tool.
+    Edited,
+    /// A file dozo does not manage is in the way of a generated file.
+    Conflict,
+}
+
+impl Problem {
+    fn message(self) -> &'static str {
+        match self {
+            Problem::Missing => "missing; run `dozo push`",
+            Problem::Outdated => "out of date; run `dozo push`",
+            Problem::Stale => "no longer generated; run `dozo push` to remove it",
+            Problem::Edited => {
+                "edited directly; move the change into the config directory or run `dozo push --force`"
+            }
+            Problem::Conflict => {
+                "exists but is not managed by dozo; run `dozo push --force` to replace it"
+            }
+        }
+    }
+}
+
+/// One generated file that does not match the config directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub target: String,
+    pub path: String,
+    pub problem: Problem,
+    pub message: String,
+}
+
+/// Renders every selected target in memory and fails when the files on disk
+/// do not match. `--format github` prints the findings as GitHub Actions
+/// annotations.
+pub async fn check_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let config_dir = config.config_dir();
+    let adapters = select_adapters(registry, config, target)?;
+    ensure_config_exists(config_dir)?;
+
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_verbose(verbose);
+    let manifest = Manifest::load(&ctx.config_dir)?;
+
+    let mut findings = Vec::new();
+    for adapter in adapters {
+        if let Some(status) = target_status(adapter, &ctx, &manifest)? {
+            findings.extend(findings_for(adapter.name(), &status));
+        }
+    }
+
+    if findings.is_empty() {
+        reporter.say("✅ Generated files are up to date");
+    }
+    for finding in &findings {
+        reporter.file_error(&finding.path, &finding.target, &finding.message);
+    }
+    reporter.detail("findings", &findings);
+
+    if !findings.is_empty() {
+        return Err(DozoError::OutOfDate {
+            count: findings.len(),
+            config_dir: config_dir.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+pub fn findings_for(target: &str, status: &TargetStatus) -> Vec<Finding> {
+    let finding = |path: &str, problem: Problem| Finding {
+        target: target.to_string(),
+        path: path.to_string(),
+        problem,
+        message: problem.message().to_string(),
+    };
+
+    let mut findings: Vec<Finding> = status
+        .plan
+        .pending()
+        .map(|change| {
+            let problem = match change.kind {
+                ChangeKind::Create => Problem::Missing,
+                ChangeKind::Delete => Problem::Stale,
+                _ => Problem::Outdated,
+            };
+            finding(&change.path, problem)
+        })
+        .collect();
+    findings.extend(status.edited.iter().map(|p| finding(p, Problem::Edited)));
+    findings.extend(
+        status
+            .conflicts
+            .iter()
+            .map(|p| finding(p, Problem::Conflict)),
+    );
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::push::push_adapter;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_findings() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        fs::write(config_dir.join("b.md"), "B").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let cursor = registry.find("cursor").unwrap();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let mut manifest = Manifest::default();
+
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        let findings = findings_for("cursor", &status);
+        assert_eq!(findings.len(), 3);
+        assert!(findings.iter().all(|f| f.problem == Problem::Missing));
+
+        push_adapter(cursor, &ctx, &mut manifest).await.unwrap();
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        assert!(findings_for("cursor", &status).is_empty());
+
+        // Hand-written rules next to generated ones are fine.
+        let rules_dir = temp_dir.path().join(".cursor/rules");
+        fs::write(rules_dir.join("teammate.mdc"), "Hand written").unwrap();
+        fs::write(rules_dir.join("a.mdc"), "Edited").unwrap();
+        fs::remove_file(config_dir.join("b.md")).unwrap();
+
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        let findings: Vec<_> = findings_for("cursor", &status)
+            .into_iter()
+            .map(|f| (f.path, f.problem))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (".cursorrules".to_string(), Problem::Outdated),
+                (".cursor/rules/b.mdc".to_string(), Problem::Stale),
+                (".cursor/rules/a.mdc".to_string(), Problem::Edited),
+            ]
+        );
+    }
+}
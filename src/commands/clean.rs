@@ -0,0 +1,178 @@
+use crate::adapters::{AdapterContext, AdapterRegistry, ALL_TARGETS};
+use crate::backup::backup_plans;
+use crate::config::DozoConfig;
+use crate::lock::PushLock;
+use crate::manifest::Manifest;
+use crate::report::{Reporter, TargetReport};
+use crate::sync::{apply_plans, plan_target, SyncOutcome};
+use anyhow::Result;
+use std::sync::Arc;
+
+/// Removes every file dozo generated, as recorded in the manifest, and the
+/// directories left empty. Hand-written content is kept.
+#[allow(clippy::too_many_arguments)]
+pub async fn clean_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+    force: bool,
+    dry_run: bool,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let ctx = AdapterContext::new(config.config_dir())
+        .with_config(config.clone())
+        .with_force(force)
+        .with_dry_run(dry_run)
+        .with_verbose(verbose);
+    clean(registry, &ctx, target, reporter)
+}
+
+fn clean(
+    registry: &AdapterRegistry,
+    ctx: &AdapterContext,
+    target: Option<&str>,
+    reporter: &Reporter,
+) -> Result<()> {
+    let original_manifest = Manifest::load(&ctx.config_dir)?;
+    let mut manifest = original_manifest.clone();
+
+    let targets: Vec<String> = match target {
+        Some(target) if target != ALL_TARGETS => registry
+            .resolve_target(target)?
+            .iter()
+            .map(|adapter| adapter.name().to_string())
+            .collect(),
+        _ => manifest.targets().into_iter().map(String::from).collect(),
+    };
+    if targets.is_empty() {
+        reporter.say("✨ Nothing to clean");
+        return Ok(());
+    }
+
+    let dry_run = ctx.is_dry_run();
+    let _lock = if dry_run {
+        None
+    } else {
+        Some(PushLock::acquire(&ctx.config_dir)?)
+    };
+    let plans = targets
+        .iter()
+        .map(|target| plan_target(&ctx.project_root, &manifest, target, Vec::new(), ctx.force))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(snapshot) = backup_plans(
+        &ctx.config_dir,
+        "clean",
+        &plans,
+        &manifest,
+        ctx.config.keep_backups,
+        &ctx.effects,
+    )? {
+        reporter.say(format_args!("💾 Saved backup snapshot {}", snapshot.id));
+    }
+    let outcomes = apply_plans(&ctx.project_root, &plans, &mut manifest, &ctx.effects)?;
+    for (target, outcome) in targets.iter().zip(&outcomes) {
+        print_clean_result(reporter, target, outcome, dry_run, ctx.verbose);
+        reporter.target(TargetReport::from_outcome(target, outcome));
+    }
+
+    if manifest != original_manifest {
+        manifest.save(&ctx.config_dir, &ctx.effects)?;
+    }
+    if dry_run {
+        reporter.planned(ctx.effects.take());
+    }
+    Ok(())
+}
+
+fn print_clean_result(
+    reporter: &Reporter,
+    target: &str,
+    outcome: &SyncOutcome,
+    dry_run: bool,
+    verbose: bool,
+) {
+    let verb = if dry_run { "Would clean" } else { "Cleaned" };
+    reporter.say(format_args!(
+        "🧹 {} {} ({} files removed, {} updated)",
+        verb,
+        target,
+        outcome.deleted.len(),
+        outcome.written.len()
+    ));
+    if verbose {
+        for path in &outcome.deleted {
+            reporter.say(format_args!("   - {} (removed)", path));
+        }
+        for path in &outcome.written {
+            reporter.say(format_args!("   - {} (dozo region removed)", path));
+        }
+    }
+    for warning in &outcome.warnings {
+        reporter.say(format_args!("⚠️  {}", warning));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::push::push_adapter;
+    use crate::error::DozoError;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_clean_keeps_user_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_dir = root.join("config");
+        fs::create_dir_all(config_dir.join("frontend")).unwrap();
+        fs::create_dir_all(config_dir.join("commands")).unwrap();
+        fs::write(config_dir.join("frontend/react.md"), "Use hooks").unwrap();
+        fs::write(config_dir.join("commands/deploy.md"), "Deploy").unwrap();
+        fs::write(root.join("CLAUDE.md"), "# My notes\n").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(root);
+        let mut manifest = Manifest::default();
+        for name in ["cursor", "claude"] {
+            push_adapter(registry.find(name).unwrap(), &ctx, &mut manifest)
+                .await
+                .unwrap();
+        }
+        fs::write(root.join(".cursor/rules/teammate.mdc"), "Mine").unwrap();
+
+        manifest.save(&config_dir, &ctx.effects).unwrap();
+        let reporter = Reporter::default();
+        clean(&registry, &ctx, Some("cursor"), &reporter).unwrap();
+
+        assert!(!root.join(".cursor/rules/frontend").exists());
+        assert!(!root.join(".cursorrules").exists());
+        assert!(root.join(".cursor/rules/teammate.mdc").exists());
+        assert!(root.join(".claude/commands/deploy.md").exists());
+
+        clean(&registry, &ctx, None, &reporter).unwrap();
+
+        assert!(!root.join(".claude").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("CLAUDE.md")).unwrap(),
+            "# My notes\n"
+        );
+        assert!(Manifest::load(&config_dir).unwrap().files.is_empty());
+        assert!(!config_dir.join(crate::lock::LOCK_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_clean_rejects_unknown_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = AdapterRegistry::with_builtin();
+        let ctx = AdapterContext::new(temp_dir.path()).with_project_root(temp_dir.path());
+
+        let err = clean(&registry, &ctx, Some("vim"), &Reporter::default()).unwrap_err();
+
+        assert!(matches!(
+            DozoError::find(&err),
+            Some(DozoError::UnknownTool { .. })
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+use crate::adapters::DevinOptions;
+use crate::cli::DevinCommand;
+use crate::commands::utils::find_rule_files;
+use crate::config::DozoConfig;
+use crate::devin::select::in_folder;
+use crate::devin::{folder_paths, knowledge_id, DevinClient, Knowledge};
+use crate::error::DozoError;
+use crate::report::Reporter;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A remote knowledge item and where it lives locally, if it was pulled.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KnowledgeEntry {
+    pub id: String,
+    /// Folder names joined with `/`; empty at the top level.
+    pub folder: String,
+    pub name: String,
+    pub trigger: String,
+    pub created_at: DateTime<Utc>,
+    pub local: bool,
+    /// Rule file that holds the item, relative to the config directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// `dozo devin list` filters.
+#[derive(Debug, Default)]
+pub struct ListFilter {
+    pub folder: Option<String>,
+    pub name: Option<Regex>,
+    pub trigger: Option<Regex>,
+    pub local: bool,
+    pub missing: bool,
+}
+
+impl ListFilter {
+    fn matches(&self, entry: &KnowledgeEntry) -> bool {
+        self.folder
+            .as_deref()
+            .is_none_or(|folder| in_folder(&entry.folder, folder.trim_matches('/')))
+            && self.name.as_ref().is_none_or(|r| r.is_match(&entry.name))
+            && self
+                .trigger
+                .as_ref()
+                .is_none_or(|r| r.is_match(&entry.trigger))
+            && (!self.local || entry.local)
+            && (!self.missing || !entry.local)
+    }
+}
+
+pub async fn devin_command(
+    config: &DozoConfig,
+    command: DevinCommand,
+    verbose: bool,
+    reporter: &Reporter,
+) -> Result<()> {
+    let options = DevinOptions::from_config(config)?;
+    let client = DevinClient::from_options(&options)?;
+    let config_dir = Path::new(config.config_dir());
+
+    match command {
+        DevinCommand::List {
+            folder,
+            name,
+            trigger,
+            local,
+            missing,
+        } => {
+            let filter = ListFilter {
+                folder,
+                name: name.as_deref().map(|p| pattern("--name", p)).transpose()?,
+                trigger: trigger
+                    .as_deref()
+                    .map(|p| pattern("--trigger", p))
+                    .transpose()?,
+                local,
+                missing,
+            };
+            let entries = knowledge_entries(&client, config_dir, config).await?;
+            let total = entries.len();
+            let entries: Vec<_> = entries
+                .into_iter()
+                .filter(|e| filter.matches(e))
+                .map(|e| KnowledgeEntry { body: None, ..e })
+                .collect();
+            if verbose {
+                reporter.say(format_args!(
+                    "📚 {} of {} knowledge items match",
+                    entries.len(),
+                    total
+                ));
+            }
+            print_table(reporter, &entries);
+            reporter.detail("knowledge", &entries);
+        }
+        DevinCommand::Show { id } => {
+            let entry = knowledge_entries(&client, config_dir, config)
+                .await?
+                .into_iter()
+                .find(|entry| entry.id == id)
+                .ok_or(DozoError::KnowledgeNotFound(id))?;
+            print_entry(reporter, &entry);
+            reporter.detail("knowledge", &entry);
+        }
+    }
+    Ok(())
+}
+
+fn pattern(flag: &str, pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).with_context(|| format!("Invalid {} pattern '{}'", flag, pattern))
+}
+
+/// Lists the remote knowledge, oldest first, with folder paths and the
+/// local rule file of each pulled item.
+pub async fn knowledge_entries(
+    client: &DevinClient,
+    config_dir: &Path,
+    config: &DozoConfig,
+) -> Result<Vec<KnowledgeEntry>> {
+    let response = client.list_knowledge().await?;
+    let folders = folder_paths(&response.folders);
+    let local = local_knowledge(config_dir, config)?;
+
+    let mut entries: Vec<_> = response
+        .knowledge
+        .into_iter()
+        .map(|knowledge: Knowledge| {
+            let folder = knowledge
+                .parent_folder_id
+                .as_ref()
+                .and_then(|id| folders.get(id))
+                .map(|path| path.join("/"))
+                .unwrap_or_default();
+            let path = local.get(&knowledge.id).cloned();
+            KnowledgeEntry {
+                local: path.is_some(),
+                path,
+                folder,
+                name: knowledge.name,
+                trigger: knowledge.trigger_description,
+                created_at: knowledge.created_at,
+                body: Some(knowledge.body),
+                id: knowledge.id,
+            }
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.created_at);
+    Ok(entries)
+}
+
+/// Maps the knowledge ID recorded in each rule file to the file's path.
+/// Backups and other hidden files are not rules and are ignored.
+fn local_knowledge(config_dir: &Path, config: &DozoConfig) -> Result<HashMap<String, String>> {
+    let mut local = HashMap::new();
+    for path in find_rule_files(config_dir, config)? {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if let Some(id) = knowledge_id(&content) {
+            let relative = path.strip_prefix(config_dir).unwrap_or(&path);
+            local.insert(id.to_string(), relative.display().to_string());
+        }
+    }
+    Ok(local)
+}
+
+fn print_table(reporter: &Reporter, entries: &[KnowledgeEntry]) {
+    if entries.is_empty() {
+        reporter.say("ℹ️  No knowledge items found");
+        return;
+    }
+
+    let rows: Vec<[String; 6]> = entries
+        .iter()
+        .map(|e| {
+            [
+                e.id.clone(),
+                e.folder.clone(),
+                e.name.clone(),
+                truncate(&e.trigger, 40),
+                e.created_at.format("%Y-%m-%d").to_string(),
+                if e.local { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+    let header = ["ID", "FOLDER", "NAME", "TRIGGER", "CREATED", "LOCAL"].map(String::from);
+    let mut widths = [0; 6];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in std::iter::once(&header).chain(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        reporter.say(cells.join("  ").trim_end());
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max - 1).collect();
+    format!("{}…", truncated)
+}
+
+fn print_entry(reporter: &Reporter, entry: &KnowledgeEntry) {
+    reporter.say(format_args!("# {}", entry.name));
+    reporter.say(format_args!("ID:      {}", entry.id));
+    if !entry.folder.is_empty() {
+        reporter.say(format_args!("Folder:  {}", entry.folder));
+    }
+    reporter.say(format_args!("Trigger: {}", entry.trigger));
+    reporter.say(format_args!(
+        "Created: {}",
+        entry.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    reporter.say(format_args!(
+        "Local:   {}",
+        entry.path.as_deref().unwrap_or("not pulled")
+    ));
+    reporter.say("");
+    reporter.say(entry.body.as_deref().unwrap_or_default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devin::mock::MockDevin;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_knowledge_entries_mark_local_items() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("devin/Frontend")).unwrap();
+        fs::write(
+            temp_dir.path().join("devin/Frontend/React.md"),
+            "<!-- Devin Knowledge ID: a -->\n\nUse hooks\n",
+        )
+        .unwrap();
+        // A backup of a rule that was deleted since.
+        let backup = temp_dir.path().join(".dozo-backups/1/files/devin");
+        fs::create_dir_all(&backup).unwrap();
+        fs::write(
+            backup.join("Deploy.md"),
+            "<!-- Devin Knowledge ID: b -->\n\nShip it\n",
+        )
+        .unwrap();
+        let server = MockDevin::start().await;
+        server.add_folder("f1", "Frontend", None);
+        server.add_folder("f2", "React", Some("f1"));
+        server.add_knowledge("a", "React", "Use hooks", Some("f2"));
+        server.add_knowledge("b", "Deploy", "Ship it", None);
+
+        let entries = knowledge_entries(&server.client(), temp_dir.path(), &DozoConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(entries[0].folder, "Frontend/React");
+        assert_eq!(entries[0].path.as_deref(), Some("devin/Frontend/React.md"));
+        assert!(!entries[1].local);
+        assert_eq!(entries[1].path, None);
+
+        let filter = ListFilter {
+            folder: Some("frontend".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.matches(&entries[0]));
+        assert!(!filter.matches(&entries[1]));
+        let filter = ListFilter {
+            name: Some(Regex::new("^Dep").unwrap()),
+            missing: true,
+            ..Default::default()
+        };
+        assert!(!filter.matches(&entries[0]));
+        assert!(filter.matches(&entries[1]));
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("a longer trigger", 8), "a longe…");
+        assert_eq!(truncate("first\nsecond", 40), "first");
+    }
+}
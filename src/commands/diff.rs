@@ -0,0 +1,61 @@
+use crate::adapters::{AdapterContext, AdapterRegistry};
+use crate::commands::push::{plan_adapter, select_adapters};
+use crate::commands::utils::ensure_config_exists;
+use crate::config::DozoConfig;
+use crate::manifest::Manifest;
+use crate::report::{Reporter, TargetReport, TargetResult};
+use anyhow::Result;
+use std::sync::Arc;
+
+/// Prints a unified diff of every file `dozo push` would change.
+pub async fn diff_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+    force: bool,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let config_dir = config.config_dir();
+    let adapters = select_adapters(registry, config, target)?;
+    ensure_config_exists(config_dir)?;
+
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_force(force)
+        .with_verbose(verbose);
+    let manifest = Manifest::load(&ctx.config_dir)?;
+
+    let mut changed = false;
+    for adapter in adapters {
+        let Some(plan) = plan_adapter(adapter, &ctx, &manifest)? else {
+            continue;
+        };
+        let mut changes = Vec::new();
+        for change in plan.pending() {
+            let diff = change.unified_diff();
+            if !reporter.is_json() {
+                print!("{}", diff);
+            }
+            changes.push(serde_json::json!({
+                "path": change.path,
+                "change": change.kind,
+                "diff": diff,
+            }));
+            changed = true;
+        }
+        for warning in &plan.warnings {
+            eprintln!("⚠️  {}", warning);
+        }
+
+        let mut report =
+            TargetReport::new(adapter.name(), TargetResult::Ok).with_detail("changes", changes);
+        report.warnings = plan.warnings;
+        reporter.target(report);
+    }
+
+    if !changed && verbose {
+        reporter.say("✅ No changes to push");
+    }
+    Ok(())
+}
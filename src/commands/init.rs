@@ -0,0 +1,392 @@
+use crate::adapters::{Adapter, AdapterContext, AdapterRegistry};
+use crate::commands::utils::{find_all_files, find_markdown_files};
+use crate::config::{DozoConfig, CONFIG_FILE_NAME, DEFAULT_CONFIG_DIR};
+use crate::error::DozoError;
+use crate::frontmatter::RuleDocument;
+use crate::manifest::{content_hash, Manifest, ManifestEntry};
+use crate::output::OutputKind;
+use crate::report::{Reporter, TargetReport, TargetResult};
+use crate::sync::path_key;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Paragraphs shorter than this are too generic to count as overlap.
+const MIN_PARAGRAPH_LEN: usize = 40;
+
+/// Two imported files that share paragraphs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Overlap {
+    pub first: String,
+    pub second: String,
+    pub shared_paragraphs: usize,
+}
+
+/// Imports every configured tool into its own namespace of the config
+/// directory, takes over the files it imported from and writes a starter
+/// `dozo.toml`.
+pub async fn init_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let ctx = AdapterContext::new(config.config_dir())
+        .with_config(config.clone())
+        .with_verbose(verbose)
+        .with_reporter(Arc::clone(reporter));
+    init(registry, &ctx, reporter).await
+}
+
+async fn init(registry: &AdapterRegistry, ctx: &AdapterContext, reporter: &Reporter) -> Result<()> {
+    let config_dir = ctx.config.config_dir();
+    let target_dir = ctx.config_dir.as_path();
+    if target_dir.exists() {
+        let count = find_all_files(target_dir)?.len();
+        if count > 0 {
+            return Err(DozoError::FileExists {
+                path: target_dir.display().to_string(),
+                count,
+            }
+            .into());
+        }
+    }
+
+    let detected: Vec<&dyn Adapter> = registry
+        .iter()
+        .filter(|a| a.capabilities().pull && a.detect(ctx))
+        .collect();
+
+    std::fs::create_dir_all(target_dir)
+        .with_context(|| format!("Failed to create directory {}", target_dir.display()))?;
+
+    if detected.is_empty() {
+        reporter.say("ℹ️  No existing tool configuration found");
+    }
+
+    let mut manifest = Manifest::load(target_dir)?;
+    let mut imported = Vec::new();
+    let mut failed = 0;
+    for adapter in &detected {
+        reporter.say(format_args!(
+            "🔄 Importing {} into {}/{}",
+            adapter.name(),
+            config_dir,
+            adapter.name()
+        ));
+        let ns_ctx = ctx.clone().with_namespace(adapter.name());
+        let result = match adapter.pull(&ns_ctx).await {
+            Ok(()) => take_over(*adapter, ctx, &mut manifest),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => {
+                imported.push(adapter.name());
+                reporter.target(TargetReport::new(adapter.name(), TargetResult::Ok));
+            }
+            Err(e) => {
+                eprintln!("❌ Failed to import {}: {:#}", adapter.name(), e);
+                reporter.target(TargetReport::failed(adapter.name(), &e));
+                failed += 1;
+            }
+        }
+    }
+    if !manifest.files.is_empty() {
+        manifest.save(target_dir, &ctx.effects)?;
+    }
+
+    let config_path = ctx.project_path(CONFIG_FILE_NAME);
+    if config_path.exists() {
+        reporter.say(format_args!("ℹ️  Keeping existing {}", CONFIG_FILE_NAME));
+    } else {
+        // Remote tools are only pushed when asked for, as with `--target all`.
+        let targets: Vec<&str> = detected
+            .iter()
+            .filter(|a| a.capabilities().push && !a.is_remote())
+            .map(|a| a.name())
+            .collect();
+        ctx.effects
+            .write(&config_path, &starter_config(config_dir, &targets))?;
+        reporter.say(format_args!("📝 Wrote {}", CONFIG_FILE_NAME));
+    }
+
+    let overlaps = find_overlaps(target_dir, &imported)?;
+    if !overlaps.is_empty() {
+        reporter.say("⚠️  Imported sources overlap; consider merging them:");
+        for overlap in &overlaps {
+            reporter.say(format_args!(
+                "   {} and {} share {} paragraph(s)",
+                overlap.first, overlap.second, overlap.shared_paragraphs
+            ));
+        }
+    }
+    reporter.detail("overlaps", &overlaps);
+
+    reporter.say(format_args!(
+        "🎉 Initialized {} from {} tool(s)",
+        config_dir,
+        imported.len()
+    ));
+
+    if failed > 0 {
+        return Err(DozoError::TargetsFailed {
+            succeeded: imported.len(),
+            failed,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Records the files `adapter` was imported from as its own outputs, so the
+/// first push replaces them with the generated files instead of writing
+/// copies next to them.
+fn take_over(adapter: &dyn Adapter, ctx: &AdapterContext, manifest: &mut Manifest) -> Result<()> {
+    for path in adapter.imported_files(ctx)? {
+        let full_path = ctx.project_root.join(&path);
+        let content = std::fs::read_to_string(&full_path)
+            .with_context(|| format!("Failed to read {}", full_path.display()))?;
+        manifest.files.insert(
+            path_key(&path),
+            ManifestEntry {
+                target: adapter.name().to_string(),
+                kind: OutputKind::File,
+                hash: content_hash(&content),
+            },
+        );
+    }
+    Ok(())
+}
+
+fn starter_config(config_dir: &str, targets: &[&str]) -> String {
+    let mut content = String::from("# dozo project settings\n\n");
+    if config_dir != DEFAULT_CONFIG_DIR {
+        content.push_str(&format!("config_dir = {:?}\n\n", config_dir));
+    }
+    if targets.is_empty() {
+        content.push_str("# targets = [\"cursor\", \"claude\"]\n");
+    } else {
+        let quoted: Vec<String> = targets.iter().map(|t| format!("{:?}", t)).collect();
+        content.push_str(&format!("targets = [{}]\n", quoted.join(", ")));
+    }
+    content
+}
+
+/// Finds imported files from different namespaces that share paragraphs.
+pub fn find_overlaps(config_dir: &Path, namespaces: &[&str]) -> Result<Vec<Overlap>> {
+    // paragraph -> files containing it
+    let mut paragraphs: BTreeMap<String, Vec<(usize, String)>> = BTreeMap::new();
+
+    for (index, namespace) in namespaces.iter().enumerate() {
+        let dir = config_dir.join(namespace);
+        if !dir.exists() {
+            continue;
+        }
+        for path in find_markdown_files(&dir)? {
+            let document = RuleDocument::load(&path)?;
+            let name = path
+                .strip_prefix(config_dir)
+                .unwrap_or(&path)
+                .display()
+                .to_string();
+            for paragraph in document.body.split("\n\n") {
+                let normalized = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+                if normalized.len() < MIN_PARAGRAPH_LEN {
+                    continue;
+                }
+                let files = paragraphs.entry(normalized).or_default();
+                if !files.iter().any(|(_, f)| *f == name) {
+                    files.push((index, name.clone()));
+                }
+            }
+        }
+    }
+
+    let mut shared: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for files in paragraphs.values() {
+        for (i, (first_ns, first)) in files.iter().enumerate() {
+            for (second_ns, second) in &files[i + 1..] {
+                if first_ns != second_ns {
+                    *shared.entry((first.clone(), second.clone())).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    Ok(shared
+        .into_iter()
+        .map(|((first, second), shared_paragraphs)| Overlap {
+            first,
+            second,
+            shared_paragraphs,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{Capabilities, ClaudeAdapter, CursorAdapter};
+    use crate::commands::check::findings_for;
+    use crate::commands::push::{push_adapters, select_adapters};
+    use crate::commands::status::target_status;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SHARED: &str = "Always run the full test suite before opening a pull request.";
+
+    #[test]
+    fn test_find_overlaps() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("cursor")).unwrap();
+        fs::create_dir_all(root.join("claude")).unwrap();
+        fs::write(
+            root.join("cursor/testing.md"),
+            format!("{}\n\nUse vitest.", SHARED),
+        )
+        .unwrap();
+        fs::write(
+            root.join("cursor/other.md"),
+            format!("{}\n\nSomething else entirely.", SHARED),
+        )
+        .unwrap();
+        fs::write(
+            root.join("claude/CLAUDE.md"),
+            format!("# Memory\n\n{}\n", SHARED.replace(' ', "  ")),
+        )
+        .unwrap();
+
+        let overlaps = find_overlaps(root, &["cursor", "claude"]).unwrap();
+
+        // Files within the same namespace are not compared.
+        assert_eq!(
+            overlaps,
+            vec![
+                Overlap {
+                    first: "cursor/other.md".to_string(),
+                    second: "claude/CLAUDE.md".to_string(),
+                    shared_paragraphs: 1,
+                },
+                Overlap {
+                    first: "cursor/testing.md".to_string(),
+                    second: "claude/CLAUDE.md".to_string(),
+                    shared_paragraphs: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_starter_config() {
+        let content = starter_config(".agentic-coding", &["cursor", "claude"]);
+        let config = DozoConfig::parse(&content).unwrap();
+        assert_eq!(
+            config.targets,
+            Some(vec!["cursor".to_string(), "claude".to_string()])
+        );
+        assert_eq!(config.config_dir, None);
+
+        let config = DozoConfig::parse(&starter_config("rules", &[])).unwrap();
+        assert_eq!(config.config_dir(), "rules");
+        assert_eq!(config.targets, None);
+    }
+
+    /// A remote tool that is configured but only pushed on request.
+    struct Remote;
+
+    #[async_trait::async_trait]
+    impl Adapter for Remote {
+        fn name(&self) -> &'static str {
+            "remote"
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities {
+                push: true,
+                pull: true,
+            }
+        }
+
+        fn detect(&self, _ctx: &AdapterContext) -> bool {
+            true
+        }
+
+        fn is_remote(&self) -> bool {
+            true
+        }
+
+        async fn pull(&self, _ctx: &AdapterContext) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn registry() -> AdapterRegistry {
+        let mut registry = AdapterRegistry::empty();
+        registry.register(CursorAdapter).register(ClaudeAdapter);
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_init_leaves_remote_tools_out_of_targets() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("CLAUDE.md"), "Use tabs").unwrap();
+        let mut registry = registry();
+        registry.register(Remote);
+        let ctx = AdapterContext::new(root.join(DEFAULT_CONFIG_DIR)).with_project_root(root);
+
+        init(&registry, &ctx, &Reporter::default()).await.unwrap();
+
+        let content = fs::read_to_string(root.join(CONFIG_FILE_NAME)).unwrap();
+        let config = DozoConfig::parse(&content).unwrap();
+        assert_eq!(config.targets, Some(vec!["claude".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_init_then_push_does_not_duplicate_imported_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".cursor/rules")).unwrap();
+        fs::create_dir_all(root.join(".claude/commands")).unwrap();
+        fs::write(root.join("CLAUDE.md"), "# Notes\n\nUse tabs\n").unwrap();
+        fs::write(
+            root.join(".cursor/rules/ts.mdc"),
+            "---\ndescription: TypeScript\nglobs: \nalwaysApply: true\n---\nPrefer const",
+        )
+        .unwrap();
+        fs::write(root.join(".claude/commands/deploy.md"), "Deploy").unwrap();
+        let registry = registry();
+        let config = DozoConfig::default();
+        let ctx = AdapterContext::new(root.join(DEFAULT_CONFIG_DIR)).with_project_root(root);
+
+        init(&registry, &ctx, &Reporter::default()).await.unwrap();
+        let adapters = select_adapters(&registry, &config, None).unwrap();
+        push_adapters(&adapters, &ctx, false, false).await.unwrap();
+
+        // The imported files are replaced, not copied.
+        assert!(!root.join(".cursor/rules/ts.mdc").exists());
+        assert!(root.join(".cursor/rules/cursor/ts.mdc").exists());
+        let claude_md = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert_eq!(claude_md.matches("Use tabs").count(), 1);
+        assert_eq!(claude_md.matches("Prefer const").count(), 1);
+        assert_eq!(
+            fs::read_to_string(root.join(".claude/commands/deploy.md")).unwrap(),
+            "Deploy"
+        );
+        let mut cursor_rules = String::new();
+        for path in find_all_files(&root.join(".cursor/rules")).unwrap() {
+            cursor_rules.push_str(&fs::read_to_string(path).unwrap());
+        }
+        assert_eq!(cursor_rules.matches("Use tabs").count(), 1);
+        assert_eq!(cursor_rules.matches("Prefer const").count(), 1);
+
+        let manifest = Manifest::load(&ctx.config_dir).unwrap();
+        for adapter in adapters {
+            let status = target_status(adapter, &ctx, &manifest).unwrap().unwrap();
+            assert!(findings_for(adapter.name(), &status).is_empty());
+        }
+    }
+}
@@ -1,48 +1,99 @@
-use crate::commands::utils::copy_hierarchy;
+use crate::adapters::{AdapterContext, ClaudeOptions};
+use crate::commands::pull::{print_pull_summary, report_source};
+use crate::commands::utils::find_all_files;
+use crate::error::DozoError;
+use crate::frontmatter::{RuleDocument, RuleMetadata};
+use crate::region;
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn pull_from(target_dir: &Path, verbose: bool) -> Result<()> {
+pub fn pull_from(ctx: &AdapterContext) -> Result<()> {
     let mut pulled_files = Vec::new();
     let mut has_content = false;
+    let target_dir = ctx.import_dir();
+    let options = ClaudeOptions::from_config(&ctx.config)?;
 
-    let claude_file = Path::new("CLAUDE.md");
+    let claude_file = ctx.project_path(&options.output);
     if claude_file.exists() {
         let target_file = target_dir.join("CLAUDE.md");
-        std::fs::copy(claude_file, &target_file).with_context(|| {
-            format!(
-                "Failed to copy {} to {}",
-                claude_file.display(),
-                target_file.display()
-            )
-        })?;
-
-        pulled_files.push(target_file.display().to_string());
+        let content = std::fs::read_to_string(&claude_file)
+            .with_context(|| format!("Failed to read {}", claude_file.display()))?;
+        // Content dozo generated itself is not imported back.
+        let content = region::strip(&content)
+            .with_context(|| format!("Failed to parse {}", claude_file.display()))?;
+        let metadata = RuleMetadata {
+            description: Some("Project memory imported from CLAUDE.md".to_string()),
+            always_apply: Some(true),
+            ..Default::default()
+        };
+        if !content.trim().is_empty() {
+            ctx.effects
+                .write(&target_file, &RuleDocument::new(metadata, content).render())?;
+            report_source(ctx, None, &options.output, &target_file);
+            pulled_files.push(target_file.display().to_string());
+        }
         has_content = true;
     }
 
-    let commands_source = Path::new(".claude/commands");
+    let commands_source = ctx.project_path(&options.commands_dir);
     if commands_source.exists() {
-        let commands_target = target_dir.join("commands");
-        let mut command_files = copy_hierarchy(commands_source, &commands_target, None)?;
-        pulled_files.append(&mut command_files);
+        // Commands stay commands, whatever namespace the rules go to.
+        let commands_target = ctx.config_dir.join(&ctx.config.commands_dir);
+        for source_path in find_all_files(&commands_source)? {
+            let relative_path = source_path
+                .strip_prefix(&commands_source)
+                .with_context(|| {
+                    format!("Failed to get relative path for {}", source_path.display())
+                })?;
+            let target_path = commands_target.join(relative_path);
+            let content = std::fs::read_to_string(&source_path)
+                .with_context(|| format!("Failed to read {}", source_path.display()))?;
+            ctx.effects.write(&target_path, &content)?;
+            report_source(ctx, None, &source_path.display().to_string(), &target_path);
+            pulled_files.push(target_path.display().to_string());
+        }
         has_content = true;
     }
 
     if !has_content {
-        anyhow::bail!(
-            "No Claude configuration found. Expected CLAUDE.md or .claude/commands directory."
-        );
+        return Err(DozoError::ToolNotConfigured {
+            tool: "claude".to_string(),
+            reason: format!(
+                "expected a {} file or a {} directory",
+                options.output, options.commands_dir
+            ),
+        }
+        .into());
+    }
+
+    print_pull_summary(ctx, "Claude", &pulled_files);
+    Ok(())
+}
+
+/// The memory file and the commands `pull_from` reads, relative to the
+/// project root.
+pub fn imported_files(ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+    let options = ClaudeOptions::from_config(&ctx.config)?;
+    let mut files = Vec::new();
+
+    if ctx.project_path(&options.output).exists() {
+        files.push(PathBuf::from(&options.output));
     }
 
-    println!("✅ Pulled {} files from Claude", pulled_files.len());
-    if verbose {
-        for file in &pulled_files {
-            println!("   - {}", file);
+    let commands_source = ctx.project_path(&options.commands_dir);
+    if commands_source.exists() {
+        for source_path in find_all_files(&commands_source)? {
+            let relative_path = source_path
+                .strip_prefix(&commands_source)
+                .with_context(|| {
+                    format!("Failed to get relative path for {}", source_path.display())
+                })?;
+            files.push(Path::new(&options.commands_dir).join(relative_path));
         }
     }
 
-    Ok(())
+    files.sort();
+    Ok(files)
 }
 
 #[cfg(test)]
@@ -57,17 +108,82 @@ mod tests {
         let target_dir = temp_dir.path().join("target");
         fs::create_dir_all(&target_dir).unwrap();
 
-        let old_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
+        let ctx = AdapterContext::new(&target_dir).with_project_root(temp_dir.path());
+        let result = pull_from(&ctx);
+
+        let err = result.unwrap_err();
+        assert!(matches!(
+            DozoError::find(&err),
+            Some(DozoError::ToolNotConfigured { tool, .. }) if tool == "claude"
+        ));
+    }
+
+    #[test]
+    fn test_pull_from_writes_front_matter() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_dir = temp_dir.path().join("target");
+        fs::create_dir_all(&target_dir).unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "# Memory\nUse tabs").unwrap();
+
+        let ctx = AdapterContext::new(&target_dir).with_project_root(temp_dir.path());
+        pull_from(&ctx).unwrap();
 
-        let result = pull_from(&target_dir, false);
+        let document = RuleDocument::load(&target_dir.join("CLAUDE.md")).unwrap();
+        assert_eq!(document.metadata.always_apply, Some(true));
+        assert_eq!(document.body, "# Memory\nUse tabs");
+    }
+
+    #[test]
+    fn test_pull_from_namespace_keeps_commands_at_top_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_dir = temp_dir.path().join("target");
+        let commands_dir = temp_dir.path().join(".claude/commands");
+        fs::create_dir_all(&commands_dir).unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "Use tabs").unwrap();
+        fs::write(commands_dir.join("deploy.md"), "Deploy").unwrap();
+
+        let ctx = AdapterContext::new(&target_dir)
+            .with_project_root(temp_dir.path())
+            .with_namespace("claude");
+        pull_from(&ctx).unwrap();
+
+        assert!(target_dir.join("claude/CLAUDE.md").exists());
+        assert!(target_dir.join("commands/deploy.md").exists());
+    }
+
+    #[test]
+    fn test_pull_from_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_dir = temp_dir.path().join("target");
+        let commands_dir = temp_dir.path().join(".claude/commands");
+        fs::create_dir_all(&commands_dir).unwrap();
+        fs::write(temp_dir.path().join("CLAUDE.md"), "Use tabs").unwrap();
+        fs::write(commands_dir.join("deploy.md"), "Deploy").unwrap();
+
+        let ctx = AdapterContext::new(&target_dir)
+            .with_project_root(temp_dir.path())
+            .with_dry_run(true);
+        pull_from(&ctx).unwrap();
+
+        assert_eq!(ctx.effects.take().len(), 2);
+        assert!(!target_dir.exists());
+    }
+
+    #[test]
+    fn test_pull_from_skips_generated_region() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_dir = temp_dir.path().join("target");
+        fs::create_dir_all(&target_dir).unwrap();
+        fs::write(
+            temp_dir.path().join("CLAUDE.md"),
+            region::apply(Some("# Notes\n"), "Generated").unwrap(),
+        )
+        .unwrap();
 
-        std::env::set_current_dir(old_dir).unwrap();
+        let ctx = AdapterContext::new(&target_dir).with_project_root(temp_dir.path());
+        pull_from(&ctx).unwrap();
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("No Claude configuration found"));
+        let document = RuleDocument::load(&target_dir.join("CLAUDE.md")).unwrap();
+        assert_eq!(document.body, "# Notes\n");
     }
 }
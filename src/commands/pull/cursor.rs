@@ -1,56 +1,158 @@
-use crate::commands::utils::copy_hierarchy_with_extension_change;
+use crate::adapters::{AdapterContext, CursorOptions};
+use crate::commands::pull::{print_pull_summary, report_source};
+use crate::error::DozoError;
+use crate::frontmatter::{RuleDocument, RuleMetadata};
+use crate::manifest::Manifest;
+use crate::mdc;
+use crate::region;
+use crate::sync::path_key;
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
-pub fn pull_from(target_dir: &Path, verbose: bool) -> Result<()> {
+pub fn pull_from(ctx: &AdapterContext) -> Result<()> {
     let mut pulled_files = Vec::new();
     let mut has_content = false;
+    let target_dir = ctx.import_dir();
+    let options = CursorOptions::from_config(&ctx.config)?;
 
-    let cursor_rules_dir = Path::new(".cursor/rules");
+    let cursor_rules_dir = ctx.project_path(&options.rules_dir);
     if cursor_rules_dir.exists() {
-        let copied_files =
-            copy_hierarchy_with_extension_change(cursor_rules_dir, target_dir, "mdc", "md")?;
+        let copied_files = import_mdc_rules(ctx, &cursor_rules_dir, &target_dir)?;
         pulled_files.extend(copied_files);
         has_content = true;
 
-        if verbose {
-            println!("   Found .cursor/rules/ directory (modern format)");
+        if ctx.verbose {
+            ctx.reporter
+                .say("   Found .cursor/rules/ directory (modern format)");
         }
     }
 
-    let cursorrules_file = Path::new(".cursorrules");
+    let cursorrules_file = ctx.project_path(&options.cursorrules);
     if cursorrules_file.exists() {
         let target_file = target_dir.join("cursorrules.md");
-        std::fs::copy(cursorrules_file, &target_file).with_context(|| {
-            format!(
-                "Failed to copy {} to {}",
-                cursorrules_file.display(),
-                target_file.display()
-            )
-        })?;
-
-        pulled_files.push(target_file.display().to_string());
+        let content = std::fs::read_to_string(&cursorrules_file)
+            .with_context(|| format!("Failed to read {}", cursorrules_file.display()))?;
+        // Content dozo generated itself is not imported back.
+        let content = region::strip(&content)
+            .with_context(|| format!("Failed to parse {}", cursorrules_file.display()))?;
+        let metadata = RuleMetadata {
+            description: Some("Rules imported from .cursorrules".to_string()),
+            always_apply: Some(true),
+            ..Default::default()
+        };
+        if !content.trim().is_empty() {
+            ctx.effects
+                .write(&target_file, &RuleDocument::new(metadata, content).render())?;
+            report_source(ctx, None, &options.cursorrules, &target_file);
+            pulled_files.push(target_file.display().to_string());
+        }
         has_content = true;
 
-        if verbose {
-            println!("   Found .cursorrules file (legacy format)");
+        if ctx.verbose {
+            ctx.reporter
+                .say("   Found .cursorrules file (legacy format)");
         }
     }
 
     if !has_content {
-        anyhow::bail!(
-            "No Cursor configuration found. Expected .cursor/rules/ directory or .cursorrules file."
-        );
+        return Err(DozoError::ToolNotConfigured {
+            tool: "cursor".to_string(),
+            reason: format!(
+                "expected a {} directory or a {} file",
+                options.rules_dir, options.cursorrules
+            ),
+        }
+        .into());
     }
 
-    println!("✅ Pulled {} files from Cursor", pulled_files.len());
-    if verbose {
-        for file in &pulled_files {
-            println!("   - {}", file);
+    print_pull_summary(ctx, "Cursor", &pulled_files);
+    Ok(())
+}
+
+/// The `.mdc` rules and the `.cursorrules` file `pull_from` reads, relative
+/// to the project root.
+pub fn imported_files(ctx: &AdapterContext) -> Result<Vec<PathBuf>> {
+    let options = CursorOptions::from_config(&ctx.config)?;
+    let mut files = Vec::new();
+
+    let rules_dir = ctx.project_path(&options.rules_dir);
+    for entry in WalkDir::new(&rules_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("mdc") {
+            continue;
         }
+        let relative_path = path
+            .strip_prefix(&rules_dir)
+            .with_context(|| format!("Failed to get relative path for {}", path.display()))?;
+        files.push(Path::new(&options.rules_dir).join(relative_path));
     }
 
-    Ok(())
+    if ctx.project_path(&options.cursorrules).exists() {
+        files.push(PathBuf::from(&options.cursorrules));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Converts every `.mdc` rule into a markdown file with canonical front-matter,
+/// preserving the directory hierarchy. Rules dozo generated are skipped.
+fn import_mdc_rules(
+    ctx: &AdapterContext,
+    rules_dir: &Path,
+    target_dir: &Path,
+) -> Result<Vec<String>> {
+    let mut created_files = Vec::new();
+    let manifest = Manifest::load(&ctx.config_dir)?;
+    let generated: HashSet<&str> = manifest
+        .entries_for("cursor")
+        .map(|(path, _)| path.as_str())
+        .collect();
+
+    for entry in WalkDir::new(rules_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let source_path = entry.path();
+        if source_path.extension().and_then(|ext| ext.to_str()) != Some("mdc") {
+            continue;
+        }
+        let project_path = source_path
+            .strip_prefix(&ctx.project_root)
+            .unwrap_or(source_path);
+        if generated.contains(path_key(project_path).as_str()) {
+            if ctx.verbose {
+                ctx.reporter.say(format_args!(
+                    "   Skipping {}: generated by dozo",
+                    project_path.display()
+                ));
+            }
+            continue;
+        }
+
+        let relative_path = source_path.strip_prefix(rules_dir).with_context(|| {
+            format!("Failed to get relative path for {}", source_path.display())
+        })?;
+        let target_path = target_dir.join(relative_path).with_extension("md");
+
+        let content = std::fs::read_to_string(source_path)
+            .with_context(|| format!("Failed to read {}", source_path.display()))?;
+        ctx.effects
+            .write(&target_path, &mdc::parse(&content).render())?;
+        report_source(ctx, None, &source_path.display().to_string(), &target_path);
+
+        created_files.push(target_path.display().to_string());
+    }
+
+    created_files.sort();
+    Ok(created_files)
 }
 
 #[cfg(test)]
@@ -65,17 +167,72 @@ mod tests {
         let target_dir = temp_dir.path().join("target");
         fs::create_dir_all(&target_dir).unwrap();
 
-        let old_dir = std::env::current_dir().unwrap();
-        std::env::set_current_dir(temp_dir.path()).unwrap();
+        let ctx = AdapterContext::new(&target_dir).with_project_root(temp_dir.path());
+        let result = pull_from(&ctx);
+
+        let err = result.unwrap_err();
+        assert!(matches!(
+            DozoError::find(&err),
+            Some(DozoError::ToolNotConfigured { tool, .. }) if tool == "cursor"
+        ));
+    }
+
+    #[test]
+    fn test_pull_from_parses_mdc_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let target_dir = temp_dir.path().join("target");
+        let rules_dir = temp_dir.path().join(".cursor/rules/frontend");
+        fs::create_dir_all(&target_dir).unwrap();
+        fs::create_dir_all(&rules_dir).unwrap();
+        fs::write(
+            rules_dir.join("react.mdc"),
+            "---\ndescription: React rules\nglobs: src/**/*.tsx\nalwaysApply: false\n---\nUse hooks\n",
+        )
+        .unwrap();
+
+        let ctx = AdapterContext::new(&target_dir).with_project_root(temp_dir.path());
+        pull_from(&ctx).unwrap();
+
+        let pulled = fs::read_to_string(target_dir.join("frontend/react.md")).unwrap();
+        assert!(!pulled.contains("alwaysApply"));
+
+        let document = RuleDocument::parse(&pulled).unwrap();
+        assert_eq!(
+            document.metadata.description.as_deref(),
+            Some("React rules")
+        );
+        assert_eq!(document.metadata.globs, vec!["src/**/*.tsx"]);
+        assert_eq!(document.metadata.always_apply, Some(false));
+        assert_eq!(document.body, "Use hooks\n");
+    }
+
+    #[tokio::test]
+    async fn test_pull_from_skips_generated_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("general.md"), "Be kind").unwrap();
 
-        let result = pull_from(&target_dir, false);
+        let registry = crate::adapters::AdapterRegistry::with_builtin();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        crate::commands::push::push_adapters(
+            &[registry.find("cursor").unwrap()],
+            &ctx,
+            false,
+            false,
+        )
+        .await
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(".cursor/rules/teammate.mdc"),
+            "Hand written",
+        )
+        .unwrap();
 
-        std::env::set_current_dir(old_dir).unwrap();
+        pull_from(&ctx.clone().with_namespace("cursor")).unwrap();
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("No Cursor configuration found"));
+        let imported = config_dir.join("cursor");
+        assert!(imported.join("teammate.md").exists());
+        assert!(!imported.join("general.md").exists());
     }
 }
@@ -1,144 +1,261 @@
-use crate::devin::{DevinClient, DevinError, Knowledge};
-use anyhow::Result;
-use std::fs;
-use std::path::Path;
+use crate::adapters::{AdapterContext, DevinOptions};
+use crate::commands::pull::report_source;
+use crate::devin::select::Selection;
+use crate::devin::{folder_paths, knowledge_id_comment, DevinClient, Knowledge};
+use crate::frontmatter::{RuleDocument, RuleMetadata};
+use crate::report::{TargetReport, TargetResult};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 
-pub async fn pull_from(target_dir: &Path, verbose: bool) -> Result<()> {
-    let config_dir = target_dir
-        .to_str()
-        .ok_or_else(|| anyhow::anyhow!("Invalid target directory path"))?;
-
-    pull_from_devin(config_dir, verbose)
+pub async fn pull_from(ctx: &AdapterContext, client: &DevinClient) -> Result<()> {
+    // Devin knowledge always gets its own directory.
+    let import_dir = match &ctx.namespace {
+        Some(_) => ctx.import_dir(),
+        None => ctx.config_dir.join("devin"),
+    };
+    let options = DevinOptions::from_config(&ctx.config)?;
+    pull_from_devin(ctx, client, &options, &import_dir)
         .await
-        .map_err(|e| anyhow::anyhow!("Failed to pull from Devin: {}", e))?;
+        .context("Failed to pull from Devin")?;
 
     Ok(())
 }
 
-async fn pull_from_devin(config_dir: &str, verbose: bool) -> Result<(), DevinError> {
+async fn pull_from_devin(
+    ctx: &AdapterContext,
+    client: &DevinClient,
+    options: &DevinOptions,
+    devin_dir: &Path,
+) -> Result<()> {
+    let verbose = ctx.verbose;
+    let reporter = &ctx.reporter;
     if verbose {
-        println!("🔄 Pulling knowledge from Devin...");
+        reporter.say("🔄 Pulling knowledge from Devin...");
     }
 
-    let client = DevinClient::new()?;
-
+    ctx.effects.api_call("GET", "/knowledge");
     let response = client.list_knowledge().await?;
 
     let total_count = response.knowledge.len();
 
     if verbose {
-        println!("📚 Found {} knowledge items", total_count);
+        reporter.say(format_args!("📚 Found {} knowledge items", total_count));
     }
 
-    let current_dir = std::env::current_dir()?;
-    let folder_names = get_folder_names(&current_dir);
-
-    if verbose {
-        println!("🔍 Filtering for project folders: {:?}", folder_names);
+    let selection = if ctx.select_all {
+        Selection::all()
+    } else {
+        Selection::new(&options.select)?
+    };
+    let folders = folder_paths(&response.folders);
+    let mut decisions = Vec::new();
+    let mut selected = Vec::new();
+    for knowledge in response.knowledge {
+        let folder = knowledge
+            .parent_folder_id
+            .as_ref()
+            .and_then(|id| folders.get(id))
+            .cloned()
+            .unwrap_or_default();
+        let decision = selection.decide(&knowledge, &folder.join("/"));
+        if ctx.explain {
+            reporter.say(format_args!(
+                "{} {} ({}): {}",
+                if decision.keep {
+                    "✅ keep"
+                } else {
+                    "⏭️  skip"
+                },
+                knowledge.name,
+                knowledge.id,
+                decision.reason
+            ));
+            decisions.push(serde_json::json!({
+                "id": knowledge.id,
+                "name": knowledge.name,
+                "folder": folder.join("/"),
+                "keep": decision.keep,
+                "reason": decision.reason,
+            }));
+        }
+        if decision.keep {
+            selected.push((knowledge, folder));
+        }
     }
 
-    let relevant_knowledge: Vec<_> = response
-        .knowledge
-        .into_iter()
-        .filter(|knowledge| is_relevant_to_project(knowledge, &folder_names))
-        .collect();
-
     if verbose {
-        println!(
-            "🎯 Found {} relevant knowledge items for this project",
-            relevant_knowledge.len()
-        );
+        reporter.say(format_args!(
+            "🎯 Selected {} of {} knowledge items",
+            selected.len(),
+            total_count
+        ));
     }
 
-    let devin_dir = format!("{}/devin", config_dir);
-    fs::create_dir_all(&devin_dir)?;
-
     let mut saved_files = Vec::new();
-
-    for knowledge in relevant_knowledge {
-        let file_path = save_knowledge_to_file(&knowledge, &devin_dir, verbose)?;
-        saved_files.push(file_path);
+    for (knowledge, folder) in selected {
+        let path = save_knowledge_to_file(ctx, &knowledge, devin_dir, &folder)?;
+        saved_files.push(path.display().to_string());
     }
-
-    println!(
-        "✅ Pulled {} files from Devin (filtered from {} total)",
+    reporter.say(format_args!(
+        "{} {} files from Devin (filtered from {} total)",
+        if ctx.is_dry_run() {
+            "📝 Would pull"
+        } else {
+            "✅ Pulled"
+        },
         saved_files.len(),
         total_count
-    );
+    ));
     if verbose {
         for file in &saved_files {
-            println!("   - {}", file);
+            reporter.say(format_args!("   - {}", file));
         }
     }
 
+    let mut report =
+        TargetReport::new("devin", TargetResult::Ok).with_detail("available", total_count);
+    if ctx.explain {
+        report = report.with_detail("selection", decisions);
+    }
+    report.written = saved_files;
+    reporter.target(report);
+
     Ok(())
 }
 
+/// Writes a knowledge item to `devin_dir`, under one subdirectory per
+/// folder in `folder`.
 fn save_knowledge_to_file(
+    ctx: &AdapterContext,
     knowledge: &Knowledge,
-    devin_dir: &str,
-    verbose: bool,
-) -> Result<String, DevinError> {
-    let safe_name = sanitize_filename(&knowledge.name);
-    let filename = format!("{}/{}.md", devin_dir, safe_name);
-
-    let content = format!(
-        "# {}\n\n<!-- Devin Knowledge ID: {} -->\n<!-- Trigger: {} -->\n<!-- Created: {} -->\n\n{}\n",
-        knowledge.name,
-        knowledge.id,
-        knowledge.trigger_description,
+    devin_dir: &Path,
+    folder: &[String],
+) -> Result<PathBuf> {
+    let mut path = devin_dir.to_path_buf();
+    for name in folder {
+        path.push(sanitize_filename(name));
+    }
+    path.push(format!("{}.md", sanitize_filename(&knowledge.name)));
+
+    let metadata = RuleMetadata {
+        title: Some(knowledge.name.clone()),
+        description: Some(knowledge.trigger_description.clone()),
+        tags: vec!["devin".to_string()],
+        ..Default::default()
+    };
+    let body = format!(
+        "{}\n<!-- Created: {} -->\n\n{}\n",
+        knowledge_id_comment(&knowledge.id),
         knowledge.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
         knowledge.body
     );
 
-    fs::write(&filename, content)?;
+    ctx.effects
+        .write(&path, &RuleDocument::new(metadata, body).render())?;
 
-    if verbose {
-        println!("📝 Saved: {}", filename);
+    report_source(ctx, Some(&knowledge.id), &knowledge.name, &path);
+    if ctx.is_dry_run() {
+        ctx.reporter.say(format_args!(
+            "📝 Would save \"{}\" as {}",
+            knowledge.name,
+            path.display()
+        ));
+    } else if ctx.verbose {
+        ctx.reporter
+            .say(format_args!("📝 Saved: {}", path.display()));
     }
 
-    Ok(filename)
+    Ok(path)
 }
 
 fn sanitize_filename(name: &str) -> String {
-    name.chars()
+    let name = name
+        .chars()
         .map(|c| match c {
             '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
             c => c,
         })
         .collect::<String>()
         .trim()
-        .to_string()
+        .to_string();
+    // Keep every name a single, visible path component.
+    match name.strip_prefix('.') {
+        Some(rest) => format!("_{}", rest),
+        None if name.is_empty() => "_".to_string(),
+        None => name,
+    }
 }
 
-fn get_folder_names(current_dir: &Path) -> Vec<String> {
-    current_dir
-        .components()
-        .filter_map(|component| {
-            if let std::path::Component::Normal(name) = component {
-                name.to_str().map(|s| s.to_lowercase())
-            } else {
-                None
-            }
-        })
-        .collect()
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DozoConfig;
+    use crate::devin::mock::MockDevin;
+    use crate::report::{OutputFormat, Reporter};
+    use chrono::Utc;
+    use std::sync::Arc;
+    use tempfile::TempDir;
 
-fn is_relevant_to_project(knowledge: &Knowledge, folder_names: &[String]) -> bool {
-    let trigger_lower = knowledge.trigger_description.to_lowercase();
+    #[tokio::test]
+    async fn test_pull_applies_selection_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let devin_dir = temp_dir.path().join("devin");
+        let server = MockDevin::start().await;
+        server.add_folder("f1", "Frontend", None);
+        server.add_folder("f2", "Archive", None);
+        server.add_knowledge("a", "React", "Use hooks", Some("f1"));
+        server.add_knowledge("b", "Old React", "Use classes", Some("f2"));
+        server.add_knowledge("c", "Deploy", "Ship on Fridays", None);
+        let config =
+            DozoConfig::parse("[adapters.devin.select]\nfolders = [\"Frontend\"]\nids = [\"c\"]")
+                .unwrap();
+        let options = DevinOptions::from_config(&config).unwrap();
+        let reporter = Arc::new(Reporter::new(OutputFormat::Json, "pull"));
+        let ctx = AdapterContext::new(temp_dir.path())
+            .with_explain(true)
+            .with_reporter(Arc::clone(&reporter));
 
-    let name_lower = knowledge.name.to_lowercase();
+        pull_from_devin(&ctx, &server.client(), &options, &devin_dir)
+            .await
+            .unwrap();
 
-    let body_lower = knowledge.body.to_lowercase();
+        assert!(devin_dir.join("Frontend/React.md").exists());
+        assert!(devin_dir.join("Deploy.md").exists());
+        assert!(!devin_dir.join("Archive").exists());
+        let report = reporter.take(None);
+        let selection = &report.targets[0].details["selection"];
+        assert_eq!(selection[1]["keep"], false);
+        assert_eq!(selection[1]["reason"], "matches no include rule");
 
-    for folder_name in folder_names {
-        if trigger_lower.contains(folder_name)
-            || name_lower.contains(folder_name)
-            || body_lower.contains(folder_name)
-        {
-            return true;
-        }
+        let ctx = AdapterContext::new(temp_dir.path()).with_select_all(true);
+        pull_from_devin(&ctx, &server.client(), &options, &devin_dir)
+            .await
+            .unwrap();
+        assert!(devin_dir.join("Archive/Old React.md").exists());
     }
 
-    false
+    #[test]
+    fn test_save_knowledge_mirrors_folders() {
+        let temp_dir = TempDir::new().unwrap();
+        let devin_dir = temp_dir.path().join("devin");
+        let ctx = AdapterContext::new(temp_dir.path());
+        let knowledge = Knowledge {
+            id: "note-1".to_string(),
+            name: "Hooks: rules".to_string(),
+            body: "Use hooks".to_string(),
+            trigger_description: "When editing components".to_string(),
+            parent_folder_id: Some("f2".to_string()),
+            created_at: Utc::now(),
+        };
+        let folder = vec!["Frontend".to_string(), "..".to_string()];
+
+        save_knowledge_to_file(&ctx, &knowledge, &devin_dir, &folder).unwrap();
+
+        let saved = devin_dir.join("Frontend/_./Hooks_ rules.md");
+        let document = RuleDocument::load(&saved).unwrap();
+        assert_eq!(document.metadata.title.as_deref(), Some("Hooks: rules"));
+        assert!(document
+            .body
+            .contains("<!-- Devin Knowledge ID: note-1 -->"));
+    }
 }
@@ -2,36 +2,107 @@ pub mod claude;
 pub mod cursor;
 pub mod devin;
 
-use crate::cli::validate_tool_name;
+use crate::adapters::{AdapterContext, AdapterRegistry};
 use crate::commands::utils::find_all_files;
+use crate::config::DozoConfig;
+use crate::error::DozoError;
+use crate::report::{Reporter, SourceItem, TargetReport, TargetResult};
 use anyhow::{Context, Result};
 use std::path::Path;
+use std::sync::Arc;
 
-pub async fn pull_command(config_dir: &str, from: &str, merge: bool, verbose: bool) -> Result<()> {
-    validate_tool_name(from).map_err(|e| anyhow::anyhow!(e))?;
+#[allow(clippy::too_many_arguments)]
+pub async fn pull_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    from: &str,
+    merge: bool,
+    dry_run: bool,
+    all: bool,
+    explain: bool,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let config_dir = config.config_dir();
+    let adapter = registry.find(from)?;
+    if !adapter.capabilities().pull {
+        return Err(DozoError::Unsupported {
+            tool: from.to_string(),
+            operation: "pull".to_string(),
+        }
+        .into());
+    }
 
-    println!("🔄 Pulling configuration from {}...", from);
+    reporter.say(format_args!("🔄 Pulling configuration from {}...", from));
 
     let target_dir = Path::new(config_dir);
     if !merge && target_dir.exists() {
         let file_count = find_all_files(target_dir)?.len();
         if file_count > 0 {
-            anyhow::bail!(
-                "Configuration directory '{}' already contains {} files. Use --merge to merge with existing configuration.",
-                config_dir, file_count
-            );
+            return Err(DozoError::FileExists {
+                path: config_dir.to_string(),
+                count: file_count,
+            }
+            .into());
         }
     }
 
-    std::fs::create_dir_all(target_dir)
-        .with_context(|| format!("Failed to create directory {}", target_dir.display()))?;
+    if !dry_run {
+        std::fs::create_dir_all(target_dir)
+            .with_context(|| format!("Failed to create directory {}", target_dir.display()))?;
+    }
 
-    match from {
-        "cursor" => cursor::pull_from(target_dir, verbose)?,
-        "claude" => claude::pull_from(target_dir, verbose)?,
-        "devin" => devin::pull_from(target_dir, verbose).await?,
-        _ => anyhow::bail!("Unsupported tool: {}", from),
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_dry_run(dry_run)
+        .with_select_all(all)
+        .with_explain(explain)
+        .with_verbose(verbose)
+        .with_reporter(Arc::clone(reporter));
+    if let Err(e) = adapter.pull(&ctx).await {
+        reporter.target(TargetReport::failed(from, &e));
+        return Err(e);
     }
 
+    if dry_run {
+        reporter.planned(ctx.effects.take());
+    }
     Ok(())
 }
+
+/// Summary line for a finished pull. The files are added to the report
+/// under the adapter's name.
+pub fn print_pull_summary(ctx: &AdapterContext, tool: &str, files: &[String]) {
+    let reporter = &ctx.reporter;
+    if ctx.is_dry_run() {
+        reporter.say(format_args!(
+            "📝 Would pull {} files from {}",
+            files.len(),
+            tool
+        ));
+    } else {
+        reporter.say(format_args!(
+            "✅ Pulled {} files from {}",
+            files.len(),
+            tool
+        ));
+    }
+    if ctx.verbose {
+        for file in files {
+            reporter.say(format_args!("   - {}", file));
+        }
+    }
+
+    let mut report = TargetReport::new(&tool.to_lowercase(), TargetResult::Ok);
+    report.written = files.to_vec();
+    reporter.target(report);
+}
+
+/// Records one imported item in the report.
+pub fn report_source(ctx: &AdapterContext, id: Option<&str>, name: &str, path: &Path) {
+    ctx.reporter.source(SourceItem {
+        id: id.map(String::from),
+        name: name.to_string(),
+        path: path.display().to_string(),
+    });
+}
@@ -1,48 +1,214 @@
-use crate::commands::utils::{copy_hierarchy, get_project_name, read_and_combine_markdown_files};
+use crate::adapters::{AdapterContext, ClaudeOptions};
+use crate::commands::utils::{
+    collect_rule_sections, combine_sections, find_markdown_files, get_project_name, RuleSection,
+};
+use crate::output::Output;
+use crate::project;
+use crate::template::{self, heading_anchor};
 use anyhow::{Context, Result};
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub fn generate_files(config_path: &Path, force: bool) -> Result<Vec<String>> {
-    let mut generated_files = Vec::new();
+/// Built-in template used when the config directory has none.
+pub const DEFAULT_TEMPLATE: &str = "# {{ project_name }} - Claude Memory
 
-    let main_file = Path::new("CLAUDE.md");
-    if main_file.exists() && !force {
-        anyhow::bail!(
-            "File '{}' already exists. Use --force to overwrite.",
-            main_file.display()
-        );
+## Project
+- **Name**: {{ project_name }}
+{{ commands }}{{ rules }}";
+
+/// Renders `CLAUDE.md` (as a managed region) and the Claude commands.
+pub fn render(ctx: &AdapterContext) -> Result<Vec<Output>> {
+    let options = ClaudeOptions::from_config(&ctx.config)?;
+
+    // Hand-written notes outside the dozo region are kept.
+    let mut outputs = vec![Output::region(
+        &options.output,
+        render_memory(ctx, &options)?,
+    )];
+
+    let commands_source = ctx.config_dir.join(&ctx.config.commands_dir);
+    if commands_source.exists() {
+        for source_path in find_markdown_files(&commands_source)? {
+            let relative_path = source_path
+                .strip_prefix(&commands_source)
+                .with_context(|| {
+                    format!("Failed to get relative path for {}", source_path.display())
+                })?;
+            let content = std::fs::read_to_string(&source_path)
+                .with_context(|| format!("Failed to read {}", source_path.display()))?;
+            outputs.push(Output::file(
+                Path::new(&options.commands_dir).join(relative_path),
+                content,
+            ));
+        }
     }
 
-    let combined_content = read_and_combine_markdown_files(config_path.to_str().unwrap())?;
-    let project_name = get_project_name();
-    let final_content = format!(
-        "# {} - Claude Memory\n\n## プロジェクト情報\n- **プロジェクト名**: {}\n\n## コマンド例\n```bash\n# プロジェクトのビルド\nnpm run build\n\n# テストの実行\nnpm test\n\n# 開発サーバーの起動\nnpm run dev\n```{}",
-        project_name, project_name, combined_content
+    Ok(outputs)
+}
+
+/// Renders the memory file from the template and the rule sections.
+pub fn render_memory(ctx: &AdapterContext, options: &ClaudeOptions) -> Result<String> {
+    let sections = collect_rule_sections(&ctx.config_dir, &ctx.config)?;
+
+    let mut variables: BTreeMap<String, String> = ctx.config.variables.clone();
+    variables.insert("project_name".to_string(), get_project_name());
+    variables.insert(
+        "config_dir".to_string(),
+        ctx.config_dir.display().to_string(),
+    );
+    variables.insert(
+        "commands".to_string(),
+        project::commands_section(&project::detect_commands(&ctx.project_root)),
     );
+    variables.insert("toc".to_string(), table_of_contents(&sections));
+    variables.insert("rules".to_string(), combine_sections(&sections));
 
-    std::fs::write(main_file, final_content)
-        .with_context(|| format!("Failed to write to {}", main_file.display()))?;
-    generated_files.push(main_file.display().to_string());
+    let (source, template) = load_template(ctx, options)?;
+    template::render(&template, &variables)
+        .with_context(|| format!("Failed to render template {}", source))
+}
 
-    let commands_source = config_path.join("commands");
-    if commands_source.exists() {
-        let commands_target = Path::new(".claude/commands");
-
-        if commands_target.exists() {
-            if !force {
-                anyhow::bail!(
-                    "Directory '{}' already exists. Use --force to overwrite.",
-                    commands_target.display()
-                );
+fn load_template(ctx: &AdapterContext, options: &ClaudeOptions) -> Result<(String, String)> {
+    let path = match &options.template {
+        Some(template) => ctx.config_dir.join(template),
+        None => {
+            let default_path = ctx
+                .config_dir
+                .join(&ctx.config.templates_dir)
+                .join("CLAUDE.md");
+            if !default_path.exists() {
+                return Ok(("(built-in)".to_string(), DEFAULT_TEMPLATE.to_string()));
             }
-            std::fs::remove_dir_all(commands_target).with_context(|| {
-                format!("Failed to remove existing {}", commands_target.display())
-            })?;
+            default_path
         }
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read template {}", path.display()))?;
+    Ok((path.display().to_string(), content))
+}
+
+fn table_of_contents(sections: &[RuleSection]) -> String {
+    sections
+        .iter()
+        .map(|section| {
+            format!(
+                "- [{}](#{})\n",
+                section.title,
+                heading_anchor(&section.title)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::DozoConfig;
+    use crate::output::OutputKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, AdapterContext) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(config_dir.join("frontend")).unwrap();
+        fs::write(config_dir.join("general.md"), "Be kind").unwrap();
+        fs::write(config_dir.join("frontend/react.md"), "Use hooks").unwrap();
+
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        (temp_dir, ctx)
+    }
+
+    #[test]
+    fn test_render_outputs() {
+        let (_temp_dir, ctx) = setup();
+        fs::create_dir_all(ctx.config_dir.join("commands/db")).unwrap();
+        fs::write(ctx.config_dir.join("commands/db/migrate.md"), "Migrate").unwrap();
+
+        let outputs = render(&ctx).unwrap();
+
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].path, Path::new("CLAUDE.md"));
+        assert_eq!(outputs[0].kind, OutputKind::Region);
+        assert!(outputs[0].content.contains("Be kind"));
+        assert!(!outputs[0].content.contains("Migrate"));
+        assert_eq!(
+            outputs[1],
+            Output::file(".claude/commands/db/migrate.md", "Migrate")
+        );
+    }
+
+    #[test]
+    fn test_render_memory_default_template() {
+        let (_temp_dir, ctx) = setup();
+        let content = render_memory(&ctx, &ClaudeOptions::default()).unwrap();
+
+        assert!(content.starts_with("# "));
+        assert!(content.contains("## Project\n"));
+        assert!(content.contains("\n## Frontend / React\n\nUse hooks\n"));
+        assert!(!content.contains("npm"));
+        assert!(!content.contains("## Commands"));
+    }
+
+    #[test]
+    fn test_render_memory_detected_commands() {
+        let (temp_dir, ctx) = setup();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"x\"\n",
+        )
+        .unwrap();
 
-        let mut command_files = copy_hierarchy(&commands_source, commands_target, None)?;
-        generated_files.append(&mut command_files);
+        let content = render_memory(&ctx, &ClaudeOptions::default()).unwrap();
+        assert!(content.contains("## Commands\n```bash\n# Build\ncargo build\n"));
+        assert!(content.find("## Commands").unwrap() < content.find("## General").unwrap());
     }
 
-    Ok(generated_files)
+    #[test]
+    fn test_render_memory_custom_template() {
+        let (_temp_dir, mut ctx) = setup();
+        fs::create_dir_all(ctx.config_dir.join("templates")).unwrap();
+        fs::write(
+            ctx.config_dir.join("templates/CLAUDE.md"),
+            "# {{ team }} handbook\n\n{{ toc }}{{ rules }}",
+        )
+        .unwrap();
+        ctx.config = DozoConfig::parse("[variables]\nteam = \"Platform\"").unwrap();
+
+        let content = render_memory(&ctx, &ClaudeOptions::default()).unwrap();
+
+        assert!(content.starts_with("# Platform handbook\n\n"));
+        assert!(content.contains("- [Frontend / React](#frontend--react)\n- [General](#general)\n"));
+        assert!(content.contains("## General\n\nBe kind"));
+        assert!(!content.contains("{{"));
+        // The template itself is not a rule.
+        assert!(!content.contains("## Templates"));
+    }
+
+    #[test]
+    fn test_render_memory_template_option() {
+        let (_temp_dir, ctx) = setup();
+        fs::write(ctx.config_dir.join("memory.tmpl"), "{{ project_name }}!").unwrap();
+        let options = ClaudeOptions {
+            template: Some("memory.tmpl".to_string()),
+            ..Default::default()
+        };
+
+        let content = render_memory(&ctx, &options).unwrap();
+        assert_eq!(content, format!("{}!", get_project_name()));
+    }
+
+    #[test]
+    fn test_render_memory_unknown_variable() {
+        let (_temp_dir, ctx) = setup();
+        fs::write(ctx.config_dir.join("memory.tmpl"), "{{ nope }}").unwrap();
+        let options = ClaudeOptions {
+            template: Some("memory.tmpl".to_string()),
+            ..Default::default()
+        };
+
+        let err = render_memory(&ctx, &options).unwrap_err();
+        assert!(format!("{:#}", err).contains("Unknown template variable 'nope'"));
+    }
 }
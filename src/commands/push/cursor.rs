@@ -1,55 +1,113 @@
-use crate::commands::utils::{copy_hierarchy, read_and_combine_markdown_files};
+use crate::adapters::{AdapterContext, CursorOptions};
+use crate::commands::utils::{find_rule_files, load_rule, read_and_combine_markdown_files};
+use crate::mdc;
+use crate::output::Output;
 use anyhow::{Context, Result};
 use std::path::Path;
 
-/// Generates Cursor configuration files (.cursor/rules/ with .mdc files and .cursorrules)
-pub fn generate_files(config_path: &Path, force: bool) -> Result<Vec<String>> {
-    let mut generated_files = Vec::new();
-
-    // 1. Generate .cursor/rules/ directory (modern format)
-    let target_dir = Path::new(".cursor/rules");
-    if target_dir.exists() {
-        if !force {
-            anyhow::bail!(
-                "Directory '{}' already exists. Use --force to overwrite.",
-                target_dir.display()
-            );
-        }
-        std::fs::remove_dir_all(target_dir)
-            .with_context(|| format!("Failed to remove existing {}", target_dir.display()))?;
-    }
-    let mut rules_files = copy_hierarchy(config_path, target_dir, Some("mdc"))?;
-    generated_files.append(&mut rules_files);
-
-    // 2. Generate .cursorrules file (legacy format)
-    let cursorrules_path = Path::new(".cursorrules");
-    if cursorrules_path.exists() && !force {
-        anyhow::bail!(
-            "File '{}' already exists. Use --force to overwrite.",
-            cursorrules_path.display()
-        );
+/// Rule files that become `.cursorrules` as they are, in order of preference.
+/// `dozo init` imports `.cursorrules` into the `cursor` namespace.
+const CURSORRULES_SOURCES: [&str; 2] = ["cursorrules.md", "cursor/cursorrules.md"];
+
+/// Renders Cursor configuration (.cursor/rules/ with .mdc files and .cursorrules)
+pub fn render(ctx: &AdapterContext) -> Result<Vec<Output>> {
+    let mut outputs = Vec::new();
+    let config_path = ctx.config_dir.as_path();
+    let options = CursorOptions::from_config(&ctx.config)?;
+
+    // 1. .cursor/rules/ directory (modern format): every rule becomes an
+    // .mdc file with a Cursor header, preserving the hierarchy.
+    let rules_dir = Path::new(&options.rules_dir);
+    for source_path in find_rule_files(config_path, &ctx.config)? {
+        let relative_path = source_path.strip_prefix(config_path).with_context(|| {
+            format!("Failed to get relative path for {}", source_path.display())
+        })?;
+        let document = load_rule(&source_path)?;
+        outputs.push(Output::file(
+            rules_dir.join(relative_path).with_extension("mdc"),
+            mdc::render(&document),
+        ));
     }
 
-    // Check if there's a specific cursorrules.md file to use
-    let cursorrules_source = config_path.join("cursorrules.md");
-    if cursorrules_source.exists() {
+    // 2. .cursorrules file (legacy format)
+    // Only the dozo region is replaced, so hand-written rules are kept.
+    let cursorrules_source = CURSORRULES_SOURCES
+        .iter()
+        .map(|path| config_path.join(path))
+        .find(|path| path.exists());
+    if let Some(cursorrules_source) = cursorrules_source {
         // Use the specific cursorrules.md file
-        let content = std::fs::read_to_string(&cursorrules_source)
-            .with_context(|| format!("Failed to read {}", cursorrules_source.display()))?;
-        std::fs::write(cursorrules_path, content)
-            .with_context(|| format!("Failed to write {}", cursorrules_path.display()))?;
+        let document = load_rule(&cursorrules_source)?;
+        outputs.push(Output::region(&options.cursorrules, document.body));
     } else {
         // Combine all markdown files into .cursorrules
-        let combined_content = read_and_combine_markdown_files(config_path.to_str().unwrap())?;
+        let combined_content = read_and_combine_markdown_files(config_path, &ctx.config)?;
         if !combined_content.trim().is_empty() {
-            std::fs::write(cursorrules_path, combined_content.trim())
-                .with_context(|| format!("Failed to write {}", cursorrules_path.display()))?;
+            outputs.push(Output::region(
+                &options.cursorrules,
+                combined_content.trim(),
+            ));
         }
     }
 
-    if cursorrules_path.exists() {
-        generated_files.push(cursorrules_path.display().to_string());
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::OutputKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_writes_mdc_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(config_dir.join("frontend")).unwrap();
+        fs::write(config_dir.join("general.md"), "# General\nBe kind").unwrap();
+        fs::write(
+            config_dir.join("frontend/react.md"),
+            "---\ndescription: React rules\nglobs: [\"src/**/*.tsx\"]\n---\nUse hooks",
+        )
+        .unwrap();
+
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let outputs = render(&ctx).unwrap();
+
+        assert_eq!(
+            outputs[0],
+            Output::file(
+                ".cursor/rules/frontend/react.mdc",
+                "---\ndescription: React rules\nglobs: src/**/*.tsx\nalwaysApply: false\n---\nUse hooks"
+            )
+        );
+        assert_eq!(
+            outputs[1],
+            Output::file(
+                ".cursor/rules/general.mdc",
+                "---\ndescription: \nglobs: \nalwaysApply: true\n---\n# General\nBe kind"
+            )
+        );
+
+        assert_eq!(outputs[2].path, Path::new(".cursorrules"));
+        assert_eq!(outputs[2].kind, OutputKind::Region);
+        assert!(!outputs[2].content.contains("globs"));
     }
 
-    Ok(generated_files)
+    #[test]
+    fn test_render_uses_imported_cursorrules() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(config_dir.join("cursor")).unwrap();
+        fs::write(config_dir.join("general.md"), "Be kind").unwrap();
+        fs::write(config_dir.join("cursor/cursorrules.md"), "Legacy rules").unwrap();
+
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let outputs = render(&ctx).unwrap();
+
+        let cursorrules = outputs.last().unwrap();
+        assert_eq!(cursorrules.path, Path::new(".cursorrules"));
+        assert_eq!(cursorrules.content, "Legacy rules");
+    }
 }
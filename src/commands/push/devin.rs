@@ -1,6 +1,406 @@
-use anyhow::Result;
+use crate::adapters::AdapterContext;
+use crate::commands::utils::find_rule_files;
+use crate::devin::{
+    knowledge_id, knowledge_id_comment, strip_knowledge_comments, DevinClient, Folder,
+    FolderRequest, Knowledge, KnowledgeRequest,
+};
+use crate::frontmatter::{split_front_matter, RuleDocument};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::path::Path;
 
-pub fn generate_files(_config_path: &Path, _force: bool) -> Result<Vec<String>> {
-    anyhow::bail!("❌ Devin push is not yet implemented. Currently only pull is supported.\n\nTo pull knowledge from Devin:\n  dozo pull --from devin\n\nNote: Requires DEVIN_API_KEY environment variable to be set.")
+/// Directory of the config directory that `dozo pull --from devin` imports
+/// into.
+const DEVIN_DIR: &str = "devin";
+
+/// Directories that `dozo init` imports the other tools into. Their rules are
+/// copies of those tools' own configuration, so they are not pushed.
+const OTHER_TOOL_DIRS: [&str; 2] = ["cursor", "claude"];
+
+/// Pushes every rule in the config directory to the Devin knowledge API and
+/// returns the files whose knowledge item was created or updated.
+///
+/// A rule whose body carries a `<!-- Devin Knowledge ID -->` comment updates
+/// that item; any other rule creates a new item, and the new ID is written
+/// back into the rule file so the next push updates it. Each rule is filed in
+/// the remote folder matching its directory, which is created if needed.
+/// Rules imported from other tools are skipped. Items whose rule file was
+/// deleted are left on the server.
+pub async fn push_to(ctx: &AdapterContext, client: &DevinClient) -> Result<Vec<String>> {
+    ctx.effects.api_call("GET", "/knowledge");
+    let response = client.list_knowledge().await?;
+    let existing: HashMap<&str, &Knowledge> = response
+        .knowledge
+        .iter()
+        .map(|knowledge| (knowledge.id.as_str(), knowledge))
+        .collect();
+
+    let mut folders = RemoteFolders::new(&response.folders);
+
+    let config_path = ctx.config_dir.as_path();
+    let mut pushed = Vec::new();
+    for source_path in find_rule_files(config_path, &ctx.config)? {
+        let relative_path = source_path.strip_prefix(config_path).with_context(|| {
+            format!("Failed to get relative path for {}", source_path.display())
+        })?;
+        if is_other_tool_import(relative_path) {
+            if ctx.verbose {
+                ctx.reporter.say(format_args!(
+                    "⏭️  Skipping {}: imported from another tool",
+                    relative_path.display()
+                ));
+            }
+            continue;
+        }
+        let content = std::fs::read_to_string(&source_path)
+            .with_context(|| format!("Failed to read {}", source_path.display()))?;
+        let document = RuleDocument::parse(&content)
+            .with_context(|| format!("Failed to parse {}", source_path.display()))?;
+        let mut request = knowledge_request(relative_path, &document);
+        request.parent_folder_id = folders
+            .resolve(ctx, client, &folder_path(relative_path))
+            .await
+            .with_context(|| format!("Failed to create folder for {}", relative_path.display()))?;
+
+        match knowledge_id(&document.body).and_then(|id| existing.get(id)) {
+            Some(knowledge) => {
+                if is_unchanged(knowledge, &request) {
+                    continue;
+                }
+                ctx.effects
+                    .api_call("PUT", &format!("/knowledge/{}", knowledge.id));
+                if !ctx.is_dry_run() {
+                    client
+                        .update_knowledge(&knowledge.id, &request)
+                        .await
+                        .with_context(|| format!("Failed to update {}", relative_path.display()))?;
+                }
+            }
+            None => {
+                ctx.effects.api_call("POST", "/knowledge");
+                if !ctx.is_dry_run() {
+                    let created = client
+                        .create_knowledge(&request)
+                        .await
+                        .with_context(|| format!("Failed to create {}", relative_path.display()))?;
+                    ctx.effects
+                        .write(&source_path, &with_knowledge_id(&content, &created.id))?;
+                }
+            }
+        }
+
+        if ctx.verbose {
+            ctx.reporter.say(format_args!(
+                "📤 {} → \"{}\"",
+                relative_path.display(),
+                request.name
+            ));
+        }
+        pushed.push(relative_path.display().to_string());
+    }
+
+    Ok(pushed)
+}
+
+/// Maps a rule to a knowledge item. The title (or file name) becomes the
+/// name and the description becomes the trigger.
+fn knowledge_request(relative_path: &Path, document: &RuleDocument) -> KnowledgeRequest {
+    let name = document.metadata.title.clone().unwrap_or_else(|| {
+        relative_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    });
+    let trigger_description = document
+        .metadata
+        .description
+        .clone()
+        .unwrap_or_else(|| name.clone());
+
+    KnowledgeRequest {
+        name,
+        body: strip_knowledge_comments(&document.body),
+        trigger_description,
+        parent_folder_id: None,
+    }
+}
+
+fn is_other_tool_import(relative_path: &Path) -> bool {
+    relative_path.components().count() > 1
+        && relative_path
+            .components()
+            .next()
+            .is_some_and(|dir| OTHER_TOOL_DIRS.iter().any(|name| dir.as_os_str() == *name))
+}
+
+/// Remote folder names for a rule: its directories, without the `devin/`
+/// directory that pulled knowledge is imported into.
+fn folder_path(relative_path: &Path) -> Vec<String> {
+    let mut names: Vec<String> = relative_path
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if names.first().map(String::as_str) == Some(DEVIN_DIR) {
+        names.remove(0);
+    }
+    names
+}
+
+/// Remote folders by parent ID and name, including the ones this push
+/// created.
+struct RemoteFolders {
+    ids: HashMap<(Option<String>, String), String>,
+}
+
+impl RemoteFolders {
+    fn new(folders: &[Folder]) -> Self {
+        Self {
+            ids: folders
+                .iter()
+                .map(|f| ((f.parent_id.clone(), f.name.clone()), f.id.clone()))
+                .collect(),
+        }
+    }
+
+    /// Returns the ID of the folder at `path`, creating missing folders.
+    /// Dry runs record the requests and return a placeholder ID.
+    async fn resolve(
+        &mut self,
+        ctx: &AdapterContext,
+        client: &DevinClient,
+        path: &[String],
+    ) -> Result<Option<String>> {
+        let mut parent_id = None;
+        for name in path {
+            let key = (parent_id.clone(), name.clone());
+            let id = match self.ids.get(&key) {
+                Some(id) => id.clone(),
+                None => {
+                    ctx.effects.api_call("POST", "/knowledge/folders");
+                    let id = if ctx.is_dry_run() {
+                        format!("new:{}", name)
+                    } else {
+                        let request = FolderRequest {
+                            name: name.clone(),
+                            parent_id: parent_id.clone(),
+                        };
+                        client.create_folder(&request).await?.id
+                    };
+                    self.ids.insert(key, id.clone());
+                    id
+                }
+            };
+            parent_id = Some(id);
+        }
+        Ok(parent_id)
+    }
+}
+
+fn is_unchanged(knowledge: &Knowledge, request: &KnowledgeRequest) -> bool {
+    knowledge.parent_folder_id == request.parent_folder_id
+        && knowledge.name == request.name
+        && knowledge.body.trim() == request.body
+        && knowledge.trigger_description == request.trigger_description
+}
+
+/// Records `id` in a rule file, replacing a stale ID comment or inserting one
+/// at the top of the body.
+fn with_knowledge_id(content: &str, id: &str) -> String {
+    let comment = knowledge_id_comment(id);
+    if let Some(old_id) = knowledge_id(content) {
+        return content.replacen(&knowledge_id_comment(old_id), &comment, 1);
+    }
+
+    let body_start = match split_front_matter(content) {
+        Some((_, body)) => content.len() - body.len(),
+        None => 0,
+    };
+    format!(
+        "{}{}\n\n{}",
+        &content[..body_start],
+        comment,
+        &content[body_start..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::devin::mock::MockDevin;
+    use chrono::Utc;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_knowledge_request_maps_front_matter() {
+        let document = RuleDocument::parse(
+            "---\ntitle: React\ndescription: When editing components\n---\n<!-- Devin Knowledge ID: note-1 -->\n\nUse hooks\n",
+        )
+        .unwrap();
+        let request = knowledge_request(Path::new("frontend/react.md"), &document);
+
+        assert_eq!(request.name, "React");
+        assert_eq!(request.trigger_description, "When editing components");
+        assert_eq!(request.body, "Use hooks");
+
+        let document = RuleDocument::parse("Be kind").unwrap();
+        let request = knowledge_request(Path::new("general.md"), &document);
+        assert_eq!(request.name, "general");
+        assert_eq!(request.trigger_description, "general");
+    }
+
+    #[test]
+    fn test_folder_path() {
+        assert_eq!(
+            folder_path(Path::new("devin/Frontend/React/Hooks.md")),
+            vec!["Frontend", "React"]
+        );
+        assert_eq!(folder_path(Path::new("backend/api.md")), vec!["backend"]);
+        assert!(folder_path(Path::new("devin/General.md")).is_empty());
+        assert!(folder_path(Path::new("general.md")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_remote_folders_reuse_and_plan_new_folders() {
+        let client = DevinClient::new("test", "http://127.0.0.1:9");
+        let ctx = AdapterContext::new("config").with_dry_run(true);
+        let mut folders = RemoteFolders::new(&[Folder {
+            id: "f1".to_string(),
+            name: "Frontend".to_string(),
+            description: None,
+            parent_id: None,
+            created_at: Utc::now(),
+        }]);
+        let path = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let id = folders.resolve(&ctx, &client, &path(&["Frontend"])).await;
+        assert_eq!(id.unwrap().as_deref(), Some("f1"));
+        assert!(ctx.effects.take().is_empty());
+
+        let id = folders
+            .resolve(&ctx, &client, &path(&["Frontend", "React"]))
+            .await;
+        assert_eq!(id.unwrap().as_deref(), Some("new:React"));
+        folders
+            .resolve(&ctx, &client, &path(&["Frontend", "React"]))
+            .await
+            .unwrap();
+        assert_eq!(ctx.effects.take().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_push_creates_updates_and_writes_ids_back() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path();
+        fs::create_dir_all(config_dir.join("devin/Frontend")).unwrap();
+        fs::write(
+            config_dir.join("devin/Frontend/React.md"),
+            "---\ntitle: React\ndescription: When working on React\n---\n<!-- Devin Knowledge ID: note-a -->\n\nUse hooks\n",
+        )
+        .unwrap();
+        fs::write(
+            config_dir.join("devin/Frontend/Vue.md"),
+            "---\ntitle: Vue\ndescription: When working on Vue\n---\n<!-- Devin Knowledge ID: note-b -->\n\nUse the composition API\n",
+        )
+        .unwrap();
+        fs::create_dir_all(config_dir.join("backend")).unwrap();
+        fs::write(config_dir.join("backend/api.md"), "Return JSON errors\n").unwrap();
+        fs::create_dir_all(config_dir.join("claude")).unwrap();
+        fs::write(config_dir.join("claude/CLAUDE.md"), "Imported notes\n").unwrap();
+
+        let server = MockDevin::start().await;
+        server.add_folder("f1", "Frontend", None);
+        server.add_knowledge("note-a", "React", "Use hooks", Some("f1"));
+        server.add_knowledge("note-b", "Vue", "Use the options API", Some("f1"));
+        let ctx = AdapterContext::new(config_dir);
+
+        let mut pushed = push_to(&ctx, &server.client()).await.unwrap();
+        pushed.sort();
+
+        assert_eq!(pushed, vec!["backend/api.md", "devin/Frontend/Vue.md"]);
+        assert_eq!(
+            server.requests(),
+            vec![
+                "GET /knowledge",
+                "POST /knowledge/folders",
+                "POST /knowledge",
+                "PUT /knowledge/note-b",
+            ]
+        );
+        let knowledge = server.knowledge();
+        let api = knowledge.iter().find(|k| k.name == "api").unwrap();
+        assert_eq!(api.body, "Return JSON errors");
+        let backend = server.folders().into_iter().find(|f| f.name == "backend");
+        assert_eq!(api.parent_folder_id, backend.map(|f| f.id));
+        assert_eq!(knowledge[1].body, "Use the composition API");
+
+        let content = fs::read_to_string(config_dir.join("backend/api.md")).unwrap();
+        assert_eq!(
+            content,
+            format!("{}\n\nReturn JSON errors\n", knowledge_id_comment(&api.id))
+        );
+
+        // Everything is in sync now, so a second push only lists.
+        let pushed = push_to(&ctx, &server.client()).await.unwrap();
+        assert!(pushed.is_empty());
+        assert_eq!(server.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_push_dry_run_sends_no_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("general.md"), "Be kind").unwrap();
+        let server = MockDevin::start().await;
+        let ctx = AdapterContext::new(temp_dir.path()).with_dry_run(true);
+
+        let pushed = push_to(&ctx, &server.client()).await.unwrap();
+
+        assert_eq!(pushed, vec!["general.md"]);
+        assert_eq!(server.requests(), vec!["GET /knowledge"]);
+        assert!(server.knowledge().is_empty());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("general.md")).unwrap(),
+            "Be kind"
+        );
+    }
+
+    #[test]
+    fn test_is_unchanged() {
+        let request = KnowledgeRequest {
+            name: "React".to_string(),
+            body: "Use hooks".to_string(),
+            trigger_description: "When editing components".to_string(),
+            parent_folder_id: None,
+        };
+        let mut knowledge = Knowledge {
+            id: "note-1".to_string(),
+            name: "React".to_string(),
+            body: "Use hooks\n".to_string(),
+            trigger_description: "When editing components".to_string(),
+            parent_folder_id: None,
+            created_at: Utc::now(),
+        };
+
+        assert!(is_unchanged(&knowledge, &request));
+        knowledge.body = "Use classes".to_string();
+        assert!(!is_unchanged(&knowledge, &request));
+    }
+
+    #[test]
+    fn test_with_knowledge_id() {
+        assert_eq!(
+            with_knowledge_id("---\ntitle: React\n---\nUse hooks\n", "note-1"),
+            "---\ntitle: React\n---\n<!-- Devin Knowledge ID: note-1 -->\n\nUse hooks\n"
+        );
+        assert_eq!(
+            with_knowledge_id("Be kind", "note-1"),
+            "<!-- Devin Knowledge ID: note-1 -->\n\nBe kind"
+        );
+        assert_eq!(
+            with_knowledge_id("<!-- Devin Knowledge ID: gone -->\n\nBe kind", "note-2"),
+            "<!-- Devin Knowledge ID: note-2 -->\n\nBe kind"
+        );
+    }
 }
@@ -2,77 +2,625 @@ pub mod claude;
 pub mod cursor;
 pub mod devin;
 
-use crate::cli::validate_tool_name;
+use crate::adapters::{Adapter, AdapterContext, AdapterRegistry, ALL_TARGETS};
+use crate::backup::backup_plans;
 use crate::commands::utils::ensure_config_exists;
+use crate::config::DozoConfig;
+use crate::error::DozoError;
+use crate::lock::PushLock;
+use crate::manifest::Manifest;
+use crate::report::{Reporter, TargetReport, TargetResult};
+use crate::sync::{apply_plans, plan_target, SyncOutcome, TargetPlan};
 use anyhow::Result;
+use std::sync::Arc;
 
+/// How many of the selected targets were pushed, failed or skipped.
+/// Targets not attempted after a fail-fast stop are in none of the counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PushSummary {
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Targets that do not support push.
+    pub skipped: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn push_command(
-    config_dir: &str,
-    target: &str,
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
     force: bool,
+    dry_run: bool,
+    keep_going: bool,
     verbose: bool,
+    reporter: &Arc<Reporter>,
 ) -> Result<()> {
-    validate_tool_name(target).map_err(|e| anyhow::anyhow!(e))?;
+    let config_dir = config.config_dir();
+    let adapters = select_adapters(registry, config, target)?;
     ensure_config_exists(config_dir)?;
 
-    println!("🚀 Pushing configuration to {}...", target);
+    reporter.say(format_args!(
+        "🚀 Pushing configuration to {}...",
+        target.unwrap_or(ALL_TARGETS)
+    ));
+
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_force(force)
+        .with_dry_run(dry_run)
+        .with_verbose(verbose)
+        .with_reporter(Arc::clone(reporter));
+
+    let skip_unconfigured = expands_all(config, target);
+    let summary = push_adapters(&adapters, &ctx, keep_going, skip_unconfigured).await?;
 
-    let tools_to_process = if target == "all" {
-        vec!["cursor", "claude", "devin"]
+    if dry_run {
+        reporter.planned(ctx.effects.take());
+    }
+    print_push_summary(reporter, &summary, adapters.len(), dry_run);
+
+    if summary.failed > 0 {
+        return Err(DozoError::TargetsFailed {
+            succeeded: summary.succeeded,
+            failed: summary.failed,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Pushes the adapters and saves the manifest. Adapters that do not support
+/// push are skipped. API-backed adapters that are not configured are skipped
+/// with `skip_unconfigured`, and fail the push before anything is written
+/// otherwise. Every target is rendered before anything is written.
+///
+/// By default the push is fail-fast and all-or-nothing: the first failure
+/// stops it and no project file is left changed. With `keep_going`, every
+/// target is attempted and the ones that rendered are written. Adapters
+/// that push to an API run after the files are written.
+///
+/// Each adapter's result is added to `ctx.reporter`.
+pub async fn push_adapters(
+    adapters: &[&dyn Adapter],
+    ctx: &AdapterContext,
+    keep_going: bool,
+    skip_unconfigured: bool,
+) -> Result<PushSummary> {
+    if !skip_unconfigured {
+        let unconfigured = adapters
+            .iter()
+            .find(|a| a.is_remote() && a.capabilities().push && !a.detect(ctx));
+        if let Some(adapter) = unconfigured {
+            return Err(DozoError::ToolNotConfigured {
+                tool: adapter.name().to_string(),
+                reason: "it was selected for push but has no credentials".to_string(),
+            }
+            .into());
+        }
+    }
+    let _lock = if ctx.is_dry_run() {
+        None
     } else {
-        vec![target]
+        Some(PushLock::acquire(&ctx.config_dir)?)
     };
+    let original_manifest = Manifest::load(&ctx.config_dir)?;
+    let mut manifest = original_manifest.clone();
+    // One report per adapter, in order; `None` means it was not run.
+    let mut results: Vec<Option<TargetReport>> = vec![None; adapters.len()];
+    let mut failed = false;
+
+    let mut plans = Vec::new();
+    let mut remote = Vec::new();
+    for (index, &adapter) in adapters.iter().enumerate() {
+        if !adapter.capabilities().push {
+            ctx.reporter.say(format_args!(
+                "⏭️  Skipping {}: push is not supported",
+                adapter.name()
+            ));
+            results[index] = Some(TargetReport::new(adapter.name(), TargetResult::Skipped));
+            continue;
+        }
+        match plan_adapter(adapter, ctx, &manifest) {
+            Ok(Some(plan)) => plans.push((index, plan)),
+            Ok(None) if skip_unconfigured && !adapter.detect(ctx) => {
+                ctx.reporter.say(format_args!(
+                    "⏭️  Skipping {}: not configured",
+                    adapter.name()
+                ));
+                results[index] = Some(TargetReport::new(adapter.name(), TargetResult::Skipped));
+            }
+            Ok(None) => remote.push((index, adapter)),
+            Err(e) => {
+                print_push_failure(adapter.name(), &e);
+                results[index] = Some(TargetReport::failed(adapter.name(), &e));
+                failed = true;
+                if !keep_going {
+                    break;
+                }
+            }
+        }
+    }
 
-    let mut success_count = 0;
-    let mut error_count = 0;
+    if failed && !keep_going {
+        eprintln!("⏪ Nothing was written; pass --keep-going to push the targets that succeeded");
+        return Ok(report_results(ctx, adapters, results));
+    }
 
-    for tool in tools_to_process {
-        match generate_tool_files(tool, config_dir, force) {
-            Ok(file_paths) => {
-                print_push_success(tool, &file_paths, verbose);
-                success_count += 1;
+    let (indices, plans): (Vec<usize>, Vec<TargetPlan>) = plans.into_iter().unzip();
+    let result = backup_plans(
+        &ctx.config_dir,
+        "push",
+        &plans,
+        &manifest,
+        ctx.config.keep_backups,
+        &ctx.effects,
+    )
+    .and_then(|snapshot| {
+        if let (Some(snapshot), true) = (snapshot, ctx.verbose) {
+            ctx.reporter
+                .say(format_args!("💾 Saved backup snapshot {}", snapshot.id));
+        }
+        apply_plans(&ctx.project_root, &plans, &mut manifest, &ctx.effects)
+    });
+    match result {
+        Ok(outcomes) => {
+            for ((index, plan), outcome) in indices.iter().zip(&plans).zip(&outcomes) {
+                print_push_success(ctx, &plan.target, outcome);
+                results[*index] = Some(TargetReport::from_outcome(&plan.target, outcome));
+            }
+        }
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            for (index, plan) in indices.iter().zip(&plans) {
+                results[*index] = Some(TargetReport::failed(&plan.target, &e));
+            }
+            failed = true;
+        }
+    }
+
+    if manifest != original_manifest {
+        manifest.save(&ctx.config_dir, &ctx.effects)?;
+    }
+
+    for (index, adapter) in remote {
+        if failed && !keep_going {
+            break;
+        }
+        results[index] = Some(match adapter.push(ctx).await {
+            Ok(written) => {
+                let outcome = SyncOutcome {
+                    written,
+                    ..Default::default()
+                };
+                print_push_success(ctx, adapter.name(), &outcome);
+                TargetReport::from_outcome(adapter.name(), &outcome)
             }
             Err(e) => {
-                eprintln!("❌ Failed to generate {} configuration: {}", tool, e);
-                error_count += 1;
+                print_push_failure(adapter.name(), &e);
+                failed = true;
+                TargetReport::failed(adapter.name(), &e)
             }
+        });
+    }
+
+    Ok(report_results(ctx, adapters, results))
+}
+
+/// Adds the per-adapter results to the report and counts them.
+fn report_results(
+    ctx: &AdapterContext,
+    adapters: &[&dyn Adapter],
+    results: Vec<Option<TargetReport>>,
+) -> PushSummary {
+    let mut summary = PushSummary::default();
+    for (adapter, result) in adapters.iter().zip(results) {
+        let report =
+            result.unwrap_or_else(|| TargetReport::new(adapter.name(), TargetResult::NotRun));
+        match report.result {
+            TargetResult::Ok => summary.succeeded += 1,
+            TargetResult::Failed => summary.failed += 1,
+            TargetResult::Skipped => summary.skipped += 1,
+            TargetResult::NotRun => {}
         }
+        ctx.reporter.target(report);
     }
+    summary
+}
 
-    print_push_summary(success_count, error_count);
-    Ok(())
+/// Plans the files `adapter` would write, or `None` for adapters that do not
+/// render project files.
+pub fn plan_adapter(
+    adapter: &dyn Adapter,
+    ctx: &AdapterContext,
+    manifest: &Manifest,
+) -> Result<Option<TargetPlan>> {
+    match adapter.render(ctx)? {
+        Some(outputs) => Ok(Some(plan_target(
+            &ctx.project_root,
+            manifest,
+            adapter.name(),
+            outputs,
+            ctx.force,
+        )?)),
+        None => Ok(None),
+    }
 }
 
-fn generate_tool_files(tool: &str, config_dir: &str, force: bool) -> Result<Vec<String>> {
-    let config_path = std::path::Path::new(config_dir);
+/// Pushes one adapter. Rendered files go through the manifest so only files
+/// dozo owns are updated or pruned.
+#[cfg(test)]
+pub async fn push_adapter(
+    adapter: &dyn Adapter,
+    ctx: &AdapterContext,
+    manifest: &mut Manifest,
+) -> Result<SyncOutcome> {
+    match plan_adapter(adapter, ctx, manifest)? {
+        Some(plan) => crate::sync::apply_plan(&ctx.project_root, &plan, manifest, &ctx.effects),
+        None => Ok(SyncOutcome {
+            written: adapter.push(ctx).await?,
+            ..Default::default()
+        }),
+    }
+}
 
-    match tool {
-        "cursor" => cursor::generate_files(config_path, force),
-        "claude" => claude::generate_files(config_path, force),
-        "devin" => devin::generate_files(config_path, force),
-        _ => anyhow::bail!("Unknown tool: {}", tool),
+/// True when `target` expands to every registered adapter instead of naming
+/// the ones to push. Remote adapters that are not configured are then skipped
+/// rather than failing the push.
+pub fn expands_all(config: &DozoConfig, target: Option<&str>) -> bool {
+    target == Some(ALL_TARGETS) && config.targets.is_none()
+}
+
+/// Resolves the adapters to push to. An explicit `--target` wins; otherwise
+/// (and for `all`) the `targets` list from `dozo.toml` is used when present.
+/// Without either, remote adapters such as Devin are left out.
+pub fn select_adapters<'a>(
+    registry: &'a AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+) -> Result<Vec<&'a dyn Adapter>> {
+    match (target, &config.targets) {
+        (Some(target), _) if target != ALL_TARGETS => Ok(registry.resolve_target(target)?),
+        (_, Some(targets)) => targets
+            .iter()
+            .map(|name| Ok(registry.find(name)?))
+            .collect(),
+        (Some(_), None) => Ok(registry.iter().collect()),
+        (None, None) => Ok(registry.iter().filter(|a| !a.is_remote()).collect()),
     }
 }
 
-fn print_push_success(tool: &str, file_paths: &[String], verbose: bool) {
-    if verbose {
-        println!("✅ Generated {} configuration:", tool);
-        for path in file_paths {
-            println!("   - {}", path);
+fn print_push_success(ctx: &AdapterContext, tool: &str, outcome: &SyncOutcome) {
+    let reporter = &ctx.reporter;
+    let dry_run = ctx.is_dry_run();
+    let file_paths = outcome.generated();
+    let verb = if dry_run {
+        "Would generate"
+    } else {
+        "Generated"
+    };
+    if ctx.verbose {
+        reporter.say(format_args!("✅ {} {} configuration:", verb, tool));
+        for path in &file_paths {
+            reporter.say(format_args!("   - {}", path));
+        }
+        for path in &outcome.deleted {
+            reporter.say(format_args!("   - {} (removed)", path));
         }
     } else {
-        println!(
-            "✅ Generated {} configuration ({} files)",
+        reporter.say(format_args!(
+            "✅ {} {} configuration ({} files)",
+            verb,
             tool,
             file_paths.len()
-        );
+        ));
+        if !outcome.deleted.is_empty() {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            reporter.say(format_args!(
+                "   {} {} stale file(s)",
+                verb,
+                outcome.deleted.len()
+            ));
+        }
+    }
+    for warning in &outcome.warnings {
+        reporter.say(format_args!("⚠️  {}", warning));
     }
 }
 
-fn print_push_summary(success_count: usize, error_count: usize) {
-    if error_count == 0 {
-        println!("🎉 Successfully pushed to {} tool(s)!", success_count);
+fn print_push_failure(tool: &str, error: &anyhow::Error) {
+    eprintln!("❌ Failed to generate {} configuration: {:#}", tool, error);
+}
+
+fn print_push_summary(reporter: &Reporter, summary: &PushSummary, selected: usize, dry_run: bool) {
+    let not_attempted = selected - summary.succeeded - summary.failed - summary.skipped;
+    if summary.failed == 0 && dry_run {
+        reporter.say(format_args!(
+            "🎉 Dry run finished for {} tool(s)",
+            summary.succeeded
+        ));
+    } else if summary.failed == 0 {
+        reporter.say(format_args!(
+            "🎉 Successfully pushed to {} tool(s)!",
+            summary.succeeded
+        ));
+    } else if not_attempted > 0 {
+        reporter.say(format_args!(
+            "⚠️  Push stopped after {} error(s); {} tool(s) were not pushed",
+            summary.failed, not_attempted
+        ));
     } else {
-        println!("⚠️  Push completed with {} error(s)", error_count);
+        reporter.say(format_args!(
+            "⚠️  Push completed with {} error(s); {} tool(s) succeeded",
+            summary.failed, summary.succeeded
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::DevinAdapter;
+    use crate::commands::check::findings_for;
+    use crate::commands::status::target_status;
+    use crate::devin::mock::{self, MockDevin};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn names(adapters: Vec<&dyn Adapter>) -> Vec<&'static str> {
+        adapters.iter().map(|a| a.name()).collect()
+    }
+
+    #[test]
+    fn test_select_adapters() {
+        let registry = AdapterRegistry::with_builtin();
+        let default_config = DozoConfig::default();
+        let config = DozoConfig::parse("targets = [\"claude\", \"cursor\"]").unwrap();
+
+        let selected = select_adapters(&registry, &default_config, None).unwrap();
+        assert_eq!(names(selected), vec!["cursor", "claude"]);
+
+        let selected = select_adapters(&registry, &default_config, Some("all")).unwrap();
+        assert_eq!(names(selected), vec!["cursor", "claude", "devin"]);
+
+        let selected = select_adapters(&registry, &config, None).unwrap();
+        assert_eq!(names(selected), vec!["claude", "cursor"]);
+
+        let selected = select_adapters(&registry, &config, Some("all")).unwrap();
+        assert_eq!(names(selected), vec!["claude", "cursor"]);
+
+        let selected = select_adapters(&registry, &config, Some("devin")).unwrap();
+        assert_eq!(names(selected), vec!["devin"]);
+
+        assert!(select_adapters(&registry, &config, Some("vim")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_push_adapter_prunes_only_generated_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        fs::write(config_dir.join("b.md"), "B").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let cursor = registry.find("cursor").unwrap();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let mut manifest = Manifest::default();
+
+        push_adapter(cursor, &ctx, &mut manifest).await.unwrap();
+        let rules_dir = temp_dir.path().join(".cursor/rules");
+        fs::write(rules_dir.join("teammate.mdc"), "Hand written").unwrap();
+        fs::remove_file(config_dir.join("b.md")).unwrap();
+
+        let outcome = push_adapter(cursor, &ctx, &mut manifest).await.unwrap();
+
+        assert_eq!(outcome.deleted.len(), 1);
+        assert!(rules_dir.join("a.mdc").exists());
+        assert!(!rules_dir.join("b.mdc").exists());
+        assert!(rules_dir.join("teammate.mdc").exists());
+    }
+
+    #[tokio::test]
+    async fn test_push_adapter_keeps_hand_written_notes() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("general.md"), "Be kind").unwrap();
+        let claude_md = temp_dir.path().join("CLAUDE.md");
+        fs::write(&claude_md, "# My notes\n").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let claude = registry.find("claude").unwrap();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let mut manifest = Manifest::default();
+
+        push_adapter(claude, &ctx, &mut manifest).await.unwrap();
+        fs::write(config_dir.join("general.md"), "Be very kind").unwrap();
+        push_adapter(claude, &ctx, &mut manifest).await.unwrap();
+
+        let content = fs::read_to_string(&claude_md).unwrap();
+        assert!(content.starts_with("# My notes\n\n<!-- dozo:begin -->\n"));
+        assert!(content.contains("Be very kind"));
+        assert_eq!(content.matches("<!-- dozo:begin -->").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_push_adapters_writes_nothing_when_a_target_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        fs::write(config_dir.join("broken.md"), "---\nglobs: [\n---\nBody").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let adapters = vec![
+            registry.find("claude").unwrap(),
+            registry.find("cursor").unwrap(),
+        ];
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+
+        let summary = push_adapters(&adapters, &ctx, false, false).await.unwrap();
+
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.succeeded, 0);
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+        assert!(!temp_dir.path().join(".cursor").exists());
+        assert!(!config_dir.join(crate::lock::LOCK_FILE_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_push_adapters_keep_going() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(config_dir.join("templates")).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        fs::write(config_dir.join("templates/CLAUDE.md"), "{{ unknown }}").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let adapters = vec![
+            registry.find("cursor").unwrap(),
+            registry.find("claude").unwrap(),
+        ];
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+
+        let summary = push_adapters(&adapters, &ctx, true, false).await.unwrap();
+
+        assert_eq!(
+            summary,
+            PushSummary {
+                succeeded: 1,
+                failed: 1,
+                skipped: 0,
+            }
+        );
+        assert!(temp_dir.path().join(".cursor/rules/a.mdc").exists());
+        assert!(!temp_dir.path().join("CLAUDE.md").exists());
+    }
+
+    #[tokio::test]
+    async fn test_default_push_leaves_devin_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        let server = MockDevin::start().await;
+
+        let mut registry = AdapterRegistry::with_builtin();
+        registry.register(DevinAdapter::with_api_key(mock::API_KEY));
+        let config = DozoConfig::parse(&format!(
+            "[adapters.devin]\nbase_url = \"{}\"",
+            server.url()
+        ))
+        .unwrap();
+        let ctx = AdapterContext::new(&config_dir)
+            .with_project_root(temp_dir.path())
+            .with_config(config.clone());
+
+        let adapters = select_adapters(&registry, &config, None).unwrap();
+        let summary = push_adapters(&adapters, &ctx, false, false).await.unwrap();
+        assert_eq!(summary.succeeded, 2);
+        assert!(server.requests().is_empty());
+        assert_eq!(fs::read_to_string(config_dir.join("a.md")).unwrap(), "A");
+
+        let adapters = select_adapters(&registry, &config, Some("all")).unwrap();
+        let summary = push_adapters(&adapters, &ctx, false, false).await.unwrap();
+        assert_eq!(summary.succeeded, 3);
+        assert_eq!(server.requests(), vec!["GET /knowledge", "POST /knowledge"]);
+
+        // Writing the new ID into a.md leaves the generated files up to date.
+        assert!(fs::read_to_string(config_dir.join("a.md"))
+            .unwrap()
+            .contains("<!-- Devin Knowledge ID: note-1 -->"));
+        let manifest = Manifest::load(&config_dir).unwrap();
+        for name in ["cursor", "claude"] {
+            let adapter = registry.find(name).unwrap();
+            let status = target_status(adapter, &ctx, &manifest).unwrap().unwrap();
+            assert!(findings_for(name, &status).is_empty(), "{} is stale", name);
+        }
+    }
+
+    /// A remote adapter without credentials.
+    struct Unconfigured;
+
+    #[async_trait::async_trait]
+    impl Adapter for Unconfigured {
+        fn name(&self) -> &'static str {
+            "remote"
+        }
+
+        fn capabilities(&self) -> crate::adapters::Capabilities {
+            crate::adapters::Capabilities {
+                push: true,
+                pull: false,
+            }
+        }
+
+        fn detect(&self, _ctx: &AdapterContext) -> bool {
+            false
+        }
+
+        fn is_remote(&self) -> bool {
+            true
+        }
+
+        async fn pull(&self, _ctx: &AdapterContext) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_named_remote_without_credentials_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let adapters = vec![registry.find("cursor").unwrap(), &Unconfigured];
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+
+        let err = push_adapters(&adapters, &ctx, true, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            DozoError::find(&err),
+            Some(DozoError::ToolNotConfigured { tool, .. }) if tool == "remote"
+        ));
+        assert!(!temp_dir.path().join(".cursor").exists());
+
+        let summary = push_adapters(&adapters, &ctx, true, true).await.unwrap();
+        assert_eq!(summary.succeeded, 1);
+        assert_eq!(summary.skipped, 1);
+    }
+
+    #[test]
+    fn test_expands_all() {
+        let config = DozoConfig::parse("targets = [\"devin\"]").unwrap();
+        assert!(expands_all(&DozoConfig::default(), Some("all")));
+        assert!(!expands_all(&config, Some("all")));
+        assert!(!expands_all(&DozoConfig::default(), Some("devin")));
+        assert!(!expands_all(&DozoConfig::default(), None));
+    }
+
+    #[tokio::test]
+    async fn test_push_adapter_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let cursor = registry.find("cursor").unwrap();
+        let ctx = AdapterContext::new(&config_dir)
+            .with_project_root(temp_dir.path())
+            .with_dry_run(true);
+        let mut manifest = Manifest::default();
+
+        let outcome = push_adapter(cursor, &ctx, &mut manifest).await.unwrap();
+
+        assert_eq!(outcome.written.len(), 2);
+        assert_eq!(ctx.effects.take().len(), 2);
+        assert!(!temp_dir.path().join(".cursor").exists());
+        assert!(!temp_dir.path().join(".cursorrules").exists());
     }
 }
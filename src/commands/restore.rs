@@ -0,0 +1,60 @@
+use crate::backup::{self, Snapshot};
+use crate::config::DozoConfig;
+use crate::effects::Effects;
+use crate::lock::PushLock;
+use crate::report::Reporter;
+use anyhow::Result;
+use std::path::Path;
+
+/// Lists the backup snapshots, or rolls the project back to one of them.
+pub async fn restore_command(
+    config: &DozoConfig,
+    snapshot: Option<&str>,
+    verbose: bool,
+    reporter: &Reporter,
+) -> Result<()> {
+    let config_dir = Path::new(config.config_dir());
+
+    let Some(id) = snapshot else {
+        let snapshots = Snapshot::list(config_dir)?;
+        print_snapshots(reporter, &snapshots);
+        reporter.detail("snapshots", &snapshots);
+        return Ok(());
+    };
+
+    let _lock = PushLock::acquire(config_dir)?;
+    let effects = Effects::default();
+    let restored = backup::restore(config_dir, Path::new(""), id, &effects)?;
+    backup::prune(config_dir, config.keep_backups.max(1), &effects)?;
+
+    if verbose {
+        for file in &restored.files {
+            reporter.say(format_args!("   - {}", file.path));
+        }
+    }
+    reporter.say(format_args!(
+        "⏪ Restored the project to before `{}` at {}",
+        restored.command,
+        restored.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+    reporter.detail("restored", &restored);
+    Ok(())
+}
+
+fn print_snapshots(reporter: &Reporter, snapshots: &[Snapshot]) {
+    if snapshots.is_empty() {
+        reporter.say("ℹ️  No backups yet");
+        return;
+    }
+
+    reporter.say("💾 Backup snapshots (oldest first):");
+    for snapshot in snapshots {
+        reporter.say(format_args!(
+            "   {}  {:<8} {} file(s)",
+            snapshot.id,
+            snapshot.command,
+            snapshot.files.len()
+        ));
+    }
+    reporter.say("Run `dozo restore <snapshot>` to undo that run and every later one.");
+}
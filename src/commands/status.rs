@@ -0,0 +1,210 @@
+use crate::adapters::{Adapter, AdapterContext, AdapterRegistry};
+use crate::commands::push::{plan_adapter, select_adapters};
+use crate::commands::utils::{ensure_config_exists, find_all_files};
+use crate::config::DozoConfig;
+use crate::manifest::Manifest;
+use crate::report::{Reporter, TargetReport, TargetResult};
+use crate::sync::{path_key, ChangeKind, TargetPlan};
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+/// What a push would change for one adapter, and where the tool's files have
+/// moved ahead of the config directory.
+#[derive(Debug, Clone, Default)]
+pub struct TargetStatus {
+    pub plan: TargetPlan,
+    /// Generated files that were edited in the tool since the last push.
+    pub edited: Vec<String>,
+    /// Existing files in the way of a generated file that dozo does not own.
+    pub conflicts: Vec<String>,
+    /// Files in the tool's directories that dozo did not generate.
+    pub untracked: Vec<String>,
+}
+
+impl TargetStatus {
+    pub fn is_clean(&self) -> bool {
+        !self.plan.has_changes()
+            && self.edited.is_empty()
+            && self.conflicts.is_empty()
+            && self.untracked.is_empty()
+    }
+}
+
+pub async fn status_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let config_dir = config.config_dir();
+    let adapters = select_adapters(registry, config, target)?;
+    ensure_config_exists(config_dir)?;
+
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_verbose(verbose);
+    let manifest = Manifest::load(&ctx.config_dir)?;
+
+    for adapter in adapters {
+        match target_status(adapter, &ctx, &manifest)? {
+            Some(status) => {
+                print_status(reporter, adapter.name(), &status);
+                reporter.target(status_report(adapter.name(), &status));
+            }
+            None => {
+                if verbose {
+                    reporter.say(format_args!(
+                        "ℹ️  {} does not generate project files",
+                        adapter.name()
+                    ));
+                }
+                reporter.target(TargetReport::new(adapter.name(), TargetResult::Skipped));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Compares what `adapter` would push against the project and the manifest.
+pub fn target_status(
+    adapter: &dyn Adapter,
+    ctx: &AdapterContext,
+    manifest: &Manifest,
+) -> Result<Option<TargetStatus>> {
+    let Some(plan) = plan_adapter(adapter, ctx, manifest)? else {
+        return Ok(None);
+    };
+
+    let mut status = TargetStatus::default();
+    for change in plan.changes.iter().filter(|c| c.kind == ChangeKind::Skip) {
+        if manifest.files.contains_key(&change.path) {
+            status.edited.push(change.path.clone());
+        } else {
+            status.conflicts.push(change.path.clone());
+        }
+    }
+
+    let known: BTreeSet<&str> = plan.changes.iter().map(|c| c.path.as_str()).collect();
+    for dir in adapter.managed_dirs(ctx)? {
+        let dir = ctx.project_root.join(dir);
+        if !dir.exists() {
+            continue;
+        }
+        for path in find_all_files(&dir)? {
+            let relative = path.strip_prefix(&ctx.project_root).unwrap_or(&path);
+            let key = path_key(relative);
+            if !known.contains(key.as_str()) && !manifest.files.contains_key(&key) {
+                status.untracked.push(key);
+            }
+        }
+    }
+    status.untracked.sort();
+
+    status.plan = plan;
+    Ok(Some(status))
+}
+
+fn print_status(reporter: &Reporter, tool: &str, status: &TargetStatus) {
+    if status.is_clean() {
+        reporter.say(format_args!("✅ {} is up to date", tool));
+        return;
+    }
+
+    reporter.say(format_args!("📋 {}:", tool));
+    for change in status.plan.pending() {
+        let label = match change.kind {
+            ChangeKind::Create => "new",
+            ChangeKind::Update => "modified",
+            _ => "deleted",
+        };
+        reporter.say(format_args!(
+            "   {:<10} {}",
+            format!("{}:", label),
+            change.path
+        ));
+    }
+    for path in &status.edited {
+        reporter.say(format_args!(
+            "   {:<10} {} (edited in {})",
+            "drifted:", path, tool
+        ));
+    }
+    for path in &status.conflicts {
+        reporter.say(format_args!(
+            "   {:<10} {} (exists, not managed by dozo)",
+            "conflict:", path
+        ));
+    }
+    for path in &status.untracked {
+        reporter.say(format_args!("   {:<10} {}", "untracked:", path));
+    }
+
+    if !status.edited.is_empty() || !status.untracked.is_empty() {
+        reporter.say(format_args!(
+            "   {} has changes that are not in the config directory; run `dozo pull --from {}` to import them",
+            tool, tool
+        ));
+    }
+}
+
+/// Status of one target as it appears in the JSON report.
+fn status_report(tool: &str, status: &TargetStatus) -> TargetReport {
+    let changes: Vec<_> = status
+        .plan
+        .pending()
+        .map(|change| serde_json::json!({"path": change.path, "change": change.kind}))
+        .collect();
+    TargetReport::new(tool, TargetResult::Ok)
+        .with_detail("clean", status.is_clean())
+        .with_detail("changes", changes)
+        .with_detail("edited", &status.edited)
+        .with_detail("conflicts", &status.conflicts)
+        .with_detail("untracked", &status.untracked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::push::push_adapter;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_target_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        fs::create_dir_all(&config_dir).unwrap();
+        fs::write(config_dir.join("a.md"), "A").unwrap();
+        fs::write(config_dir.join("b.md"), "B").unwrap();
+
+        let registry = AdapterRegistry::with_builtin();
+        let cursor = registry.find("cursor").unwrap();
+        let ctx = AdapterContext::new(&config_dir).with_project_root(temp_dir.path());
+        let mut manifest = Manifest::default();
+
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        assert_eq!(status.plan.pending().count(), 3);
+
+        push_adapter(cursor, &ctx, &mut manifest).await.unwrap();
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        assert!(status.is_clean());
+
+        let rules_dir = temp_dir.path().join(".cursor/rules");
+        fs::write(rules_dir.join("a.mdc"), "Edited").unwrap();
+        fs::write(rules_dir.join("teammate.mdc"), "Hand written").unwrap();
+        fs::write(config_dir.join("b.md"), "B2").unwrap();
+
+        let status = target_status(cursor, &ctx, &manifest).unwrap().unwrap();
+        let pending: Vec<_> = status.plan.pending().map(|c| c.path.as_str()).collect();
+        assert_eq!(pending, vec![".cursor/rules/b.mdc", ".cursorrules"]);
+        assert_eq!(status.edited, vec![".cursor/rules/a.mdc"]);
+        assert_eq!(status.untracked, vec![".cursor/rules/teammate.mdc"]);
+        assert!(status.conflicts.is_empty());
+
+        let devin = registry.find("devin").unwrap();
+        assert!(target_status(devin, &ctx, &manifest).unwrap().is_none());
+    }
+}
@@ -1,3 +1,7 @@
+use crate::config::DozoConfig;
+use crate::devin::strip_knowledge_comments;
+use crate::error::DozoError;
+use crate::frontmatter::RuleDocument;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -5,7 +9,7 @@ use walkdir::WalkDir;
 pub fn ensure_config_exists(config_dir: &str) -> Result<()> {
     let config_path = Path::new(config_dir);
     if !config_path.exists() {
-        anyhow::bail!("Configuration directory '{}' not found", config_dir);
+        return Err(DozoError::ConfigNotFound(config_dir.to_string()).into());
     }
     Ok(())
 }
@@ -49,138 +53,95 @@ pub fn find_all_files(dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(files)
 }
 
-pub fn copy_hierarchy(
-    source_dir: &Path,
-    target_dir: &Path,
-    change_extension: Option<&str>,
-) -> Result<Vec<String>> {
-    let mut created_files = Vec::new();
-
-    std::fs::create_dir_all(target_dir)
-        .with_context(|| format!("Failed to create directory {}", target_dir.display()))?;
-
-    for entry in WalkDir::new(source_dir)
+/// Finds the markdown files in the config directory that are rules, skipping
+/// hidden paths, the commands directory and `exclude` entries from `dozo.toml`.
+pub fn find_rule_files(config_path: &Path, config: &DozoConfig) -> Result<Vec<PathBuf>> {
+    let files = find_markdown_files(config_path)?
         .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let source_path = entry.path();
-
-        if source_path.extension().and_then(|ext| ext.to_str()) != Some("md") {
-            continue;
-        }
-
-        let relative_path = source_path.strip_prefix(source_dir).with_context(|| {
-            format!("Failed to get relative path for {}", source_path.display())
-        })?;
-
-        let mut target_path = target_dir.join(relative_path);
-        if let Some(new_ext) = change_extension {
-            target_path.set_extension(new_ext);
-        }
-
-        if let Some(parent) = target_path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
-        }
-
-        let content = std::fs::read_to_string(source_path)
-            .with_context(|| format!("Failed to read {}", source_path.display()))?;
-
-        std::fs::write(&target_path, content)
-            .with_context(|| format!("Failed to write {}", target_path.display()))?;
-
-        created_files.push(target_path.display().to_string());
-    }
+        .filter(|path| {
+            let Ok(relative_path) = path.strip_prefix(config_path) else {
+                return false;
+            };
+            let hidden = relative_path
+                .components()
+                .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+            // devin, cursor, claude ディレクトリは含める
+            !hidden && !config.is_excluded(relative_path)
+        })
+        .collect();
 
-    Ok(created_files)
+    Ok(files)
 }
 
-pub fn copy_hierarchy_with_extension_change(
-    source_dir: &Path,
-    target_dir: &Path,
-    from_ext: &str,
-    to_ext: &str,
-) -> Result<Vec<String>> {
-    let mut created_files = Vec::new();
-
-    for entry in WalkDir::new(source_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
-        let source_path = entry.path();
-
-        if source_path.extension().and_then(|ext| ext.to_str()) != Some(from_ext) {
-            continue;
-        }
+/// Loads a rule to render it for a tool. The Devin knowledge comments are
+/// dropped, so writing an ID back into the file does not change the output.
+pub fn load_rule(path: &Path) -> Result<RuleDocument> {
+    let mut document = RuleDocument::load(path)?;
+    document.body = strip_knowledge_comments(&document.body);
+    Ok(document)
+}
 
-        let relative_path = source_path.strip_prefix(source_dir).with_context(|| {
-            format!("Failed to get relative path for {}", source_path.display())
-        })?;
+/// A rule file prepared for inclusion in a combined document.
+#[derive(Debug, Clone)]
+pub struct RuleSection {
+    pub title: String,
+    pub body: String,
+}
 
-        let mut target_path = target_dir.join(relative_path);
-        target_path.set_extension(to_ext);
+/// Loads every rule file in order, with front-matter stripped and the section
+/// title taken from `title` or derived from the file path.
+pub fn collect_rule_sections(config_path: &Path, config: &DozoConfig) -> Result<Vec<RuleSection>> {
+    let mut sections = Vec::new();
 
-        if let Some(parent) = target_path.parent() {
-            std::fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
-        }
+    for file_path in find_rule_files(config_path, config)? {
+        let document = load_rule(&file_path)?;
 
-        let content = std::fs::read_to_string(source_path)
-            .with_context(|| format!("Failed to read {}", source_path.display()))?;
+        let relative_path = file_path
+            .strip_prefix(config_path)
+            .with_context(|| format!("Failed to get relative path for {}", file_path.display()))?;
 
-        std::fs::write(&target_path, content)
-            .with_context(|| format!("Failed to write {}", target_path.display()))?;
+        let title = match document.metadata.title {
+            Some(title) => title,
+            None => section_title_from_path(relative_path),
+        };
 
-        created_files.push(target_path.display().to_string());
+        sections.push(RuleSection {
+            title,
+            body: document.body,
+        });
     }
 
-    Ok(created_files)
+    Ok(sections)
 }
 
-pub fn read_and_combine_markdown_files(config_dir: &str) -> Result<String> {
-    let config_path = Path::new(config_dir);
-    let mut combined = String::new();
-
-    let mut files = find_markdown_files(config_path)?
-        .into_iter()
-        .filter(|path| {
-            // commands ディレクトリのみ除外（devin, cursor, claude は含める）
-            !path.components().any(|c| c.as_os_str() == "commands")
-        })
-        .collect::<Vec<_>>();
-
-    files.sort();
-
-    for file_path in files {
-        let content = std::fs::read_to_string(&file_path)
-            .with_context(|| format!("Failed to read {}", file_path.display()))?;
-
-        let relative_path = file_path
-            .strip_prefix(config_path)
-            .with_context(|| format!("Failed to get relative path for {}", file_path.display()))?;
+pub fn combine_sections(sections: &[RuleSection]) -> String {
+    sections
+        .iter()
+        .map(|section| format!("\n## {}\n\n{}\n", section.title, section.body))
+        .collect()
+}
 
-        let section_title = relative_path
-            .with_extension("")
-            .to_string_lossy()
-            .replace("/", " / ")
-            .replace("-", " ")
-            .split_whitespace()
-            .map(|word| {
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" ");
-
-        combined.push_str(&format!("\n## {}\n\n{}\n", section_title, content));
-    }
+pub fn read_and_combine_markdown_files(config_dir: &Path, config: &DozoConfig) -> Result<String> {
+    let sections = collect_rule_sections(config_dir, config)?;
+    Ok(combine_sections(&sections))
+}
 
-    Ok(combined)
+fn section_title_from_path(relative_path: &Path) -> String {
+    relative_path
+        .with_extension("")
+        .to_string_lossy()
+        .replace("/", " / ")
+        .replace("-", " ")
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[cfg(test)]
@@ -213,6 +174,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_get_project_name() {
         let name = get_project_name();
         assert!(!name.is_empty());
@@ -254,63 +216,6 @@ mod tests {
         assert_eq!(files.len(), 3);
     }
 
-    #[test]
-    fn test_copy_hierarchy() {
-        let temp_dir = TempDir::new().unwrap();
-        let source_dir = temp_dir.path().join("source");
-        let target_dir = temp_dir.path().join("target");
-
-        fs::create_dir_all(&source_dir).unwrap();
-        create_test_file(&source_dir, "test1.md", "# Test 1").unwrap();
-        create_test_file(&source_dir, "subdir/test2.md", "# Test 2").unwrap();
-        create_test_file(&source_dir, "test.txt", "Not copied").unwrap();
-
-        let result = copy_hierarchy(&source_dir, &target_dir, None).unwrap();
-        assert_eq!(result.len(), 2);
-
-        assert!(target_dir.join("test1.md").exists());
-        assert!(target_dir.join("subdir/test2.md").exists());
-        assert!(!target_dir.join("test.txt").exists());
-    }
-
-    #[test]
-    fn test_copy_hierarchy_with_extension_change() {
-        let temp_dir = TempDir::new().unwrap();
-        let source_dir = temp_dir.path().join("source");
-        let target_dir = temp_dir.path().join("target");
-
-        fs::create_dir_all(&source_dir).unwrap();
-        create_test_file(&source_dir, "test1.md", "# Test 1").unwrap();
-        create_test_file(&source_dir, "subdir/test2.md", "# Test 2").unwrap();
-
-        let result = copy_hierarchy(&source_dir, &target_dir, Some("mdc")).unwrap();
-        assert_eq!(result.len(), 2);
-
-        assert!(target_dir.join("test1.mdc").exists());
-        assert!(target_dir.join("subdir/test2.mdc").exists());
-        assert!(!target_dir.join("test1.md").exists());
-    }
-
-    #[test]
-    fn test_copy_hierarchy_with_extension_change_function() {
-        let temp_dir = TempDir::new().unwrap();
-        let source_dir = temp_dir.path().join("source");
-        let target_dir = temp_dir.path().join("target");
-
-        fs::create_dir_all(&source_dir).unwrap();
-        create_test_file(&source_dir, "test1.mdc", "# Test 1").unwrap();
-        create_test_file(&source_dir, "test2.mdc", "# Test 2").unwrap();
-        create_test_file(&source_dir, "test.txt", "Not copied").unwrap();
-
-        let result =
-            copy_hierarchy_with_extension_change(&source_dir, &target_dir, "mdc", "md").unwrap();
-        assert_eq!(result.len(), 2);
-
-        assert!(target_dir.join("test1.md").exists());
-        assert!(target_dir.join("test2.md").exists());
-        assert!(!target_dir.join("test.txt").exists());
-    }
-
     #[test]
     fn test_read_and_combine_markdown_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -320,7 +225,7 @@ mod tests {
         create_test_file(config_dir, "rules.md", "Rules content").unwrap();
         create_test_file(config_dir, "commands/deploy.md", "Deploy command").unwrap();
 
-        let result = read_and_combine_markdown_files(config_dir.to_str().unwrap()).unwrap();
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default()).unwrap();
 
         assert!(result.contains("Introduction content"));
         assert!(result.contains("Rules content"));
@@ -335,7 +240,7 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let config_dir = temp_dir.path();
 
-        let result = read_and_combine_markdown_files(config_dir.to_str().unwrap()).unwrap();
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default()).unwrap();
         assert!(result.is_empty());
     }
 
@@ -347,7 +252,7 @@ mod tests {
         create_test_file(config_dir, "frontend/react-rules.md", "React content").unwrap();
         create_test_file(config_dir, "backend/api-rules.md", "API content").unwrap();
 
-        let result = read_and_combine_markdown_files(config_dir.to_str().unwrap()).unwrap();
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default()).unwrap();
 
         assert!(result.contains("React content"));
         assert!(result.contains("API content"));
@@ -371,7 +276,7 @@ mod tests {
         // commandsディレクトリのファイル（除外されるべき）
         create_test_file(config_dir, "commands/deploy.md", "Deploy command").unwrap();
 
-        let result = read_and_combine_markdown_files(config_dir.to_str().unwrap()).unwrap();
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default()).unwrap();
 
         // 含まれるべきもの
         assert!(result.contains("General rules"));
@@ -388,4 +293,69 @@ mod tests {
         assert!(result.contains("## Cursor / Rules1"));
         assert!(result.contains("## Claude / Claude Rules"));
     }
+
+    #[test]
+    fn test_find_rule_files_applies_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path();
+
+        create_test_file(config_dir, "general.md", "General").unwrap();
+        create_test_file(config_dir, "drafts/idea.md", "Idea").unwrap();
+        create_test_file(config_dir, "cmds/deploy.md", "Deploy").unwrap();
+        create_test_file(config_dir, "commands/test.md", "Test").unwrap();
+        create_test_file(config_dir, ".backup/old.md", "Old").unwrap();
+
+        let config = DozoConfig::parse("commands_dir = \"cmds\"\nexclude = [\"drafts\"]").unwrap();
+        let files = find_rule_files(config_dir, &config).unwrap();
+        let relative: Vec<_> = files
+            .iter()
+            .map(|p| p.strip_prefix(config_dir).unwrap().to_path_buf())
+            .collect();
+
+        assert_eq!(
+            relative,
+            vec![
+                PathBuf::from("commands/test.md"),
+                PathBuf::from("general.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_and_combine_markdown_files_strips_front_matter() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path();
+
+        create_test_file(
+            config_dir,
+            "security.md",
+            "---\ndescription: Input validation\nalways_apply: true\n---\nValidate input",
+        )
+        .unwrap();
+        create_test_file(
+            config_dir,
+            "frontend/react.md",
+            "---\ntitle: React Conventions\n---\nUse hooks",
+        )
+        .unwrap();
+
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default()).unwrap();
+
+        assert!(result.contains("## Security\n\nValidate input"));
+        assert!(result.contains("## React Conventions\n\nUse hooks"));
+        assert!(!result.contains("always_apply"));
+        assert!(!result.contains("---"));
+    }
+
+    #[test]
+    fn test_read_and_combine_markdown_files_invalid_front_matter() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path();
+
+        create_test_file(config_dir, "broken.md", "---\nglobs: [unclosed\n---\nBody").unwrap();
+
+        let result = read_and_combine_markdown_files(config_dir, &DozoConfig::default());
+        assert!(result.is_err());
+        assert!(format!("{:#}", result.unwrap_err()).contains("broken.md"));
+    }
 }
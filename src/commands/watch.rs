@@ -0,0 +1,178 @@
+use crate::adapters::{Adapter, AdapterContext, AdapterRegistry};
+use crate::commands::push::{expands_all, push_adapters, select_adapters};
+use crate::commands::utils::ensure_config_exists;
+use crate::config::DozoConfig;
+use crate::report::Reporter;
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait for more events before pushing a burst of edits.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Pushes once, then re-pushes the affected targets whenever the config
+/// directory changes. Runs until interrupted. With `--format json`, a report
+/// is printed after every push.
+pub async fn watch_command(
+    registry: &AdapterRegistry,
+    config: &DozoConfig,
+    target: Option<&str>,
+    force: bool,
+    verbose: bool,
+    reporter: &Arc<Reporter>,
+) -> Result<()> {
+    let config_dir = config.config_dir();
+    let adapters: Vec<&dyn Adapter> = select_adapters(registry, config, target)?
+        .into_iter()
+        .filter(|a| a.capabilities().push)
+        .collect();
+    ensure_config_exists(config_dir)?;
+
+    let ctx = AdapterContext::new(config_dir)
+        .with_config(config.clone())
+        .with_force(force)
+        .with_verbose(verbose)
+        .with_reporter(Arc::clone(reporter));
+
+    reporter.say("🚀 Pushing configuration before watching...");
+    let skip_unconfigured = expands_all(config, target);
+    push_adapters(&adapters, &ctx, false, skip_unconfigured).await?;
+    reporter.finish(None)?;
+    ctx.effects.take();
+
+    let watch_root = std::fs::canonicalize(config_dir)
+        .with_context(|| format!("Failed to resolve {}", config_dir))?;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let _ = tx.send(event);
+    })
+    .context("Failed to start the file watcher")?;
+    watcher
+        .watch(&watch_root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", config_dir))?;
+
+    reporter.say(format_args!(
+        "👀 Watching {} for changes (Ctrl-C to stop)",
+        config_dir
+    ));
+
+    loop {
+        let first = tokio::select! {
+            event = rx.recv() => event,
+            _ = tokio::signal::ctrl_c() => None,
+        };
+        let Some(first) = first else {
+            break;
+        };
+
+        let mut events = vec![first];
+        while let Ok(Some(event)) = tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+            events.push(event);
+        }
+
+        let mut changed = BTreeSet::new();
+        for event in events {
+            match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    changed.extend(relevant_changes(&watch_root, &event.paths));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("⚠️  Watch error: {}", e),
+            }
+        }
+        if changed.is_empty() {
+            continue;
+        }
+
+        let names: Vec<_> = changed.iter().map(|p| p.display().to_string()).collect();
+        reporter.say(format_args!("🔄 Changed: {}", names.join(", ")));
+
+        let affected: Vec<&dyn Adapter> = adapters
+            .iter()
+            .copied()
+            .filter(|a| changed.iter().any(|path| a.is_affected_by(&ctx, path)))
+            .collect();
+        if affected.is_empty() {
+            reporter.say("   No targets affected");
+            continue;
+        }
+
+        // A failing push is reported and the watch keeps going.
+        let result = push_adapters(&affected, &ctx, false, skip_unconfigured).await;
+        match &result {
+            Ok(summary) if summary.failed == 0 => {}
+            Ok(summary) => {
+                eprintln!(
+                    "⚠️  {} target(s) failed; waiting for the next change",
+                    summary.failed
+                );
+            }
+            Err(e) => eprintln!("❌ Push failed: {}", e),
+        }
+        reporter.detail("changed", &names);
+        reporter.finish(result.as_ref().err())?;
+        ctx.effects.take();
+    }
+
+    reporter.say("👋 Stopped watching");
+    Ok(())
+}
+
+/// Paths relative to the config directory that can affect the generated
+/// files. Hidden files, such as editor swap files and the manifest, are
+/// ignored.
+fn relevant_changes(watch_root: &Path, paths: &[PathBuf]) -> Vec<PathBuf> {
+    paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(watch_root).ok())
+        .filter(|relative| {
+            !relative.as_os_str().is_empty()
+                && !relative
+                    .components()
+                    .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        })
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relevant_changes() {
+        let root = Path::new("/project/.agentic-coding");
+        let paths = vec![
+            root.join("frontend/react.md"),
+            root.join(".dozo-lock.json"),
+            root.join("frontend/.react.md.swp"),
+            root.to_path_buf(),
+            PathBuf::from("/elsewhere/a.md"),
+        ];
+
+        assert_eq!(
+            relevant_changes(root, &paths),
+            vec![PathBuf::from("frontend/react.md")]
+        );
+    }
+
+    #[test]
+    fn test_affected_adapters() {
+        let registry = AdapterRegistry::with_builtin();
+        let ctx = AdapterContext::new("config");
+        let affected = |path: &str| -> Vec<&str> {
+            registry
+                .iter()
+                .filter(|a| a.is_affected_by(&ctx, Path::new(path)))
+                .map(|a| a.name())
+                .collect()
+        };
+
+        assert_eq!(affected("general.md"), vec!["cursor", "claude", "devin"]);
+        assert_eq!(affected("commands/deploy.md"), vec!["claude", "devin"]);
+        assert_eq!(affected("templates/CLAUDE.md"), vec!["claude", "devin"]);
+    }
+}
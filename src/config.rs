@@ -0,0 +1,287 @@
+//! Project-level `dozo.toml` configuration.
+//!
+//! ```toml
+//! config_dir = ".agentic-coding"
+//! commands_dir = "commands"
+//! templates_dir = "templates"
+//! targets = ["cursor", "claude"]
+//! exclude = ["drafts", "**/*.wip.md"]
+//! keep_backups = 10
+//!
+//! [variables]
+//! team = "Platform"
+//!
+//! [adapters.claude]
+//! output = "CLAUDE.md"
+//! commands_dir = ".claude/commands"
+//! template = "templates/CLAUDE.md"
+//!
+//! [adapters.cursor]
+//! rules_dir = ".cursor/rules"
+//! cursorrules = ".cursorrules"
+//! ```
+//!
+//! Every key is optional. Command line flags take precedence over the file.
+
+use crate::adapters::AdapterRegistry;
+use crate::backup::DEFAULT_KEEP_BACKUPS;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const CONFIG_FILE_NAME: &str = "dozo.toml";
+pub const DEFAULT_CONFIG_DIR: &str = ".agentic-coding";
+pub const DEFAULT_COMMANDS_DIR: &str = "commands";
+pub const DEFAULT_TEMPLATES_DIR: &str = "templates";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DozoConfig {
+    /// Directory holding the rule files.
+    pub config_dir: Option<String>,
+
+    /// Subdirectory of `config_dir` with Claude commands; it is not treated
+    /// as rules.
+    #[serde(default = "default_commands_dir")]
+    pub commands_dir: String,
+
+    /// Subdirectory of `config_dir` with document templates; it is not
+    /// treated as rules.
+    #[serde(default = "default_templates_dir")]
+    pub templates_dir: String,
+
+    /// Adapters that `push` and `--target all` use. Defaults to every
+    /// registered adapter.
+    pub targets: Option<Vec<String>>,
+
+    /// Paths or glob patterns, relative to `config_dir`, that are never
+    /// treated as rules.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Number of backup snapshots to keep; 0 disables backups.
+    #[serde(default = "default_keep_backups")]
+    pub keep_backups: usize,
+
+    /// Extra variables available to templates. Built-in variables with the
+    /// same name take precedence.
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+
+    /// Per-adapter options, keyed by adapter name.
+    #[serde(default)]
+    pub adapters: BTreeMap<String, toml::Table>,
+}
+
+impl Default for DozoConfig {
+    fn default() -> Self {
+        Self {
+            config_dir: None,
+            commands_dir: default_commands_dir(),
+            templates_dir: default_templates_dir(),
+            targets: None,
+            exclude: Vec::new(),
+            keep_backups: default_keep_backups(),
+            variables: BTreeMap::new(),
+            adapters: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_commands_dir() -> String {
+    DEFAULT_COMMANDS_DIR.to_string()
+}
+
+fn default_templates_dir() -> String {
+    DEFAULT_TEMPLATES_DIR.to_string()
+}
+
+fn default_keep_backups() -> usize {
+    DEFAULT_KEEP_BACKUPS
+}
+
+impl DozoConfig {
+    /// Loads `dozo.toml` from the project root, falling back to defaults when
+    /// the file does not exist.
+    pub fn load(project_root: &Path) -> Result<Self> {
+        let path = project_root.join(CONFIG_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    pub fn config_dir(&self) -> &str {
+        self.config_dir.as_deref().unwrap_or(DEFAULT_CONFIG_DIR)
+    }
+
+    /// Deserializes the `[adapters.<name>]` table, using defaults when the
+    /// table is absent.
+    pub fn adapter_options<T>(&self, name: &str) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+    {
+        match self.adapters.get(name) {
+            None => Ok(T::default()),
+            Some(table) => T::deserialize(toml::Value::Table(table.clone()))
+                .with_context(|| format!("Invalid [adapters.{}] options", name)),
+        }
+    }
+
+    /// Checks that every adapter named in the file exists and that its
+    /// options are valid.
+    pub fn validate(&self, registry: &AdapterRegistry) -> Result<()> {
+        for target in self.targets.iter().flatten() {
+            registry
+                .find(target)
+                .map_err(|e| anyhow::anyhow!("Invalid targets entry: {}", e))?;
+        }
+
+        for name in self.adapters.keys() {
+            let adapter = registry
+                .find(name)
+                .map_err(|e| anyhow::anyhow!("Invalid [adapters.{}] section: {}", name, e))?;
+            adapter.validate_options(self)?;
+        }
+
+        for pattern in &self.exclude {
+            glob::Pattern::new(pattern)
+                .with_context(|| format!("Invalid exclude pattern '{}'", pattern))?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns true when a path relative to the config directory is excluded
+    /// from the rules, either because it lies in the commands or templates
+    /// directory or because it matches an `exclude` entry.
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        if relative_path.components().next().is_some_and(|c| {
+            c.as_os_str() == self.commands_dir.as_str()
+                || c.as_os_str() == self.templates_dir.as_str()
+        }) {
+            return true;
+        }
+
+        let patterns: Vec<_> = self
+            .exclude
+            .iter()
+            .filter_map(|p| glob::Pattern::new(p).ok())
+            .collect();
+
+        relative_path.ancestors().any(|ancestor| {
+            !ancestor.as_os_str().is_empty() && patterns.iter().any(|p| p.matches_path(ancestor))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct TestOptions {
+        output: Option<String>,
+    }
+
+    #[test]
+    fn test_load_missing_file_uses_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = DozoConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(config.config_dir(), DEFAULT_CONFIG_DIR);
+        assert_eq!(config.commands_dir, DEFAULT_COMMANDS_DIR);
+        assert_eq!(config.templates_dir, DEFAULT_TEMPLATES_DIR);
+        assert!(config.variables.is_empty());
+        assert!(config.targets.is_none());
+        assert_eq!(config.keep_backups, DEFAULT_KEEP_BACKUPS);
+    }
+
+    #[test]
+    fn test_parse_full_config() {
+        let config = DozoConfig::parse(
+            r#"
+config_dir = "rules"
+commands_dir = "cmds"
+targets = ["claude"]
+exclude = ["drafts"]
+
+[variables]
+team = "Platform"
+
+[adapters.claude]
+output = "docs/CLAUDE.md"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.config_dir(), "rules");
+        assert_eq!(config.commands_dir, "cmds");
+        assert_eq!(config.targets, Some(vec!["claude".to_string()]));
+        assert_eq!(config.variables["team"], "Platform");
+
+        let options: TestOptions = config.adapter_options("claude").unwrap();
+        assert_eq!(options.output.as_deref(), Some("docs/CLAUDE.md"));
+        let options: TestOptions = config.adapter_options("cursor").unwrap();
+        assert!(options.output.is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        let err = DozoConfig::parse("tragets = [\"claude\"]").unwrap_err();
+        assert!(err.to_string().contains("unknown field `tragets`"));
+    }
+
+    #[test]
+    fn test_adapter_options_rejects_unknown_keys() {
+        let config = DozoConfig::parse("[adapters.claude]\noutptu = \"x.md\"").unwrap();
+        let err = config.adapter_options::<TestOptions>("claude").unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown field `outptu`"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let registry = AdapterRegistry::with_builtin();
+
+        let config = DozoConfig::parse("targets = [\"cursor\", \"claude\"]").unwrap();
+        assert!(config.validate(&registry).is_ok());
+
+        let config = DozoConfig::parse("targets = [\"vim\"]").unwrap();
+        assert!(config.validate(&registry).is_err());
+
+        let config = DozoConfig::parse("[adapters.vim]\noutput = \"x\"").unwrap();
+        assert!(config.validate(&registry).is_err());
+
+        let config = DozoConfig::parse("[adapters.cursor]\nrules = \"x\"").unwrap();
+        let err = config.validate(&registry).unwrap_err();
+        assert!(format!("{:#}", err).contains("unknown field `rules`"));
+    }
+
+    #[test]
+    fn test_is_excluded() {
+        let config =
+            DozoConfig::parse("exclude = [\"drafts\", \"**/*.wip.md\", \"notes/private.md\"]")
+                .unwrap();
+
+        assert!(config.is_excluded(Path::new("commands/deploy.md")));
+        assert!(config.is_excluded(Path::new("templates/CLAUDE.md")));
+        assert!(config.is_excluded(Path::new("drafts/idea.md")));
+        assert!(config.is_excluded(Path::new("frontend/react.wip.md")));
+        assert!(config.is_excluded(Path::new("notes/private.md")));
+
+        assert!(!config.is_excluded(Path::new("general.md")));
+        assert!(!config.is_excluded(Path::new("notes/public.md")));
+        assert!(!config.is_excluded(Path::new("frontend/commands.md")));
+    }
+}
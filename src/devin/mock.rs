@@ -0,0 +1,286 @@
+//! A local stand-in for the Devin API, for tests.
+//!
+//! [`MockDevin`] serves the knowledge endpoints over HTTP on a random local
+//! port and keeps the items in memory. Tests can seed items, inspect the
+//! requests the client sent, and make the server fail, stall or return
+//! malformed JSON.
+
+use super::{DevinClient, Folder, Knowledge};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+pub const API_KEY: &str = "test-key";
+
+/// A request the server received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Received {
+    pub method: String,
+    pub path: String,
+    pub body: Value,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    knowledge: Vec<Knowledge>,
+    folders: Vec<Folder>,
+    received: Vec<Received>,
+    next_id: usize,
+    failure: Option<(u16, String)>,
+    delay: Option<Duration>,
+    malformed: bool,
+}
+
+pub struct MockDevin {
+    url: String,
+    state: Arc<Mutex<State>>,
+    server: JoinHandle<()>,
+}
+
+impl MockDevin {
+    pub async fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let shared = Arc::clone(&state);
+        let server = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&shared)));
+            }
+        });
+
+        Self { url, state, server }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// A client that talks to this server with [`API_KEY`].
+    pub fn client(&self) -> DevinClient {
+        DevinClient::new(API_KEY, &self.url)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    pub fn add_knowledge(&self, id: &str, name: &str, body: &str, folder_id: Option<&str>) {
+        self.state().knowledge.push(Knowledge {
+            id: id.to_string(),
+            name: name.to_string(),
+            body: body.to_string(),
+            trigger_description: format!("When working on {}", name),
+            parent_folder_id: folder_id.map(str::to_string),
+            created_at: Utc::now(),
+        });
+    }
+
+    pub fn add_folder(&self, id: &str, name: &str, parent_id: Option<&str>) {
+        self.state().folders.push(Folder {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            parent_id: parent_id.map(str::to_string),
+            created_at: Utc::now(),
+        });
+    }
+
+    pub fn knowledge(&self) -> Vec<Knowledge> {
+        self.state().knowledge.clone()
+    }
+
+    pub fn folders(&self) -> Vec<Folder> {
+        self.state().folders.clone()
+    }
+
+    /// `METHOD /path` of every request received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.state()
+            .received
+            .iter()
+            .map(|r| format!("{} {}", r.method, r.path))
+            .collect()
+    }
+
+    pub fn received(&self) -> Vec<Received> {
+        self.state().received.clone()
+    }
+
+    /// Answers every following request with `status` and `body`.
+    pub fn fail_with(&self, status: u16, body: &str) {
+        self.state().failure = Some((status, body.to_string()));
+    }
+
+    /// Waits `delay` before answering each request.
+    pub fn delay(&self, delay: Duration) {
+        self.state().delay = Some(delay);
+    }
+
+    /// Answers successful requests with a body that is not valid JSON.
+    pub fn malformed(&self) {
+        self.state().malformed = true;
+    }
+}
+
+impl Drop for MockDevin {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn serve(mut stream: TcpStream, state: Arc<Mutex<State>>) {
+    let Some((method, path, authorized, body)) = read_request(&mut stream).await else {
+        return;
+    };
+
+    let delay = state.lock().unwrap().delay;
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let (status, body) = {
+        let mut state = state.lock().unwrap();
+        state.received.push(Received {
+            method: method.clone(),
+            path: path.clone(),
+            body: body.clone(),
+        });
+        if !authorized {
+            (401, json!({"detail": "Unauthorized"}).to_string())
+        } else if let Some((status, body)) = state.failure.clone() {
+            (status, body)
+        } else if state.malformed {
+            (200, "{\"knowledge\": [".to_string())
+        } else {
+            let (status, value) = route(&mut state, &method, &path, body);
+            (status, value.map(|v| v.to_string()).unwrap_or_default())
+        }
+    };
+
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Reads one request: method, path, whether it had a bearer token, and the
+/// JSON body (`null` when there is none).
+async fn read_request(stream: &mut TcpStream) -> Option<(String, String, bool, Value)> {
+    let mut data = Vec::new();
+    let mut buffer = [0; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buffer[..read]);
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+
+    let mut content_length = 0;
+    let mut authorized = false;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            "authorization" => authorized = value.trim() == format!("Bearer {}", API_KEY),
+            _ => {}
+        }
+    }
+
+    while data.len() < header_end + content_length {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 {
+            break;
+        }
+        data.extend_from_slice(&buffer[..read]);
+    }
+    let body = serde_json::from_slice(&data[header_end..]).unwrap_or(Value::Null);
+
+    Some((method, path, authorized, body))
+}
+
+fn route(state: &mut State, method: &str, path: &str, body: Value) -> (u16, Option<Value>) {
+    let text = |key: &str| body[key].as_str().map(str::to_string);
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["knowledge"]) => (
+            200,
+            Some(json!({"knowledge": state.knowledge, "folders": state.folders})),
+        ),
+        ("POST", ["knowledge"]) => {
+            state.next_id += 1;
+            let knowledge = Knowledge {
+                id: format!("note-{}", state.next_id),
+                name: text("name").unwrap_or_default(),
+                body: text("body").unwrap_or_default(),
+                trigger_description: text("trigger_description").unwrap_or_default(),
+                parent_folder_id: text("parent_folder_id"),
+                created_at: Utc::now(),
+            };
+            state.knowledge.push(knowledge.clone());
+            (200, Some(json!(knowledge)))
+        }
+        ("POST", ["knowledge", "folders"]) => {
+            state.next_id += 1;
+            let folder = Folder {
+                id: format!("folder-{}", state.next_id),
+                name: text("name").unwrap_or_default(),
+                description: None,
+                parent_id: text("parent_id"),
+                created_at: Utc::now(),
+            };
+            state.folders.push(folder.clone());
+            (200, Some(json!(folder)))
+        }
+        ("PUT", ["knowledge", id]) => {
+            let Some(knowledge) = state.knowledge.iter_mut().find(|k| k.id == *id) else {
+                return (404, Some(json!({"detail": "Not found"})));
+            };
+            knowledge.name = text("name").unwrap_or_default();
+            knowledge.body = text("body").unwrap_or_default();
+            knowledge.trigger_description = text("trigger_description").unwrap_or_default();
+            knowledge.parent_folder_id = text("parent_folder_id");
+            (200, Some(json!(knowledge)))
+        }
+        ("DELETE", ["knowledge", id]) => {
+            let before = state.knowledge.len();
+            state.knowledge.retain(|k| k.id != *id);
+            if state.knowledge.len() == before {
+                (404, Some(json!({"detail": "Not found"})))
+            } else {
+                (204, None)
+            }
+        }
+        _ => (404, Some(json!({"detail": "Not found"}))),
+    }
+}
@@ -1,11 +1,22 @@
+#[cfg(test)]
+pub mod mock;
+pub mod select;
+
+use crate::adapters::DevinOptions;
+use crate::error::DozoError;
 use chrono::{DateTime, Utc};
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 
-const DEVIN_API_BASE_URL: &str = "https://api.devin.ai/v1";
+pub const DEFAULT_BASE_URL: &str = "https://api.devin.ai/v1";
+pub const API_KEY_ENV: &str = "DEVIN_API_KEY";
+pub const BASE_URL_ENV: &str = "DEVIN_API_BASE_URL";
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Knowledge {
     pub id: String,
     pub name: String,
@@ -16,7 +27,7 @@ pub struct Knowledge {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Folder {
     pub id: String,
     pub name: String,
@@ -27,6 +38,23 @@ pub struct Folder {
     pub created_at: DateTime<Utc>,
 }
 
+/// Fields sent when creating or updating a knowledge item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KnowledgeRequest {
+    pub name: String,
+    pub body: String,
+    pub trigger_description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_folder_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FolderRequest {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ListKnowledgeResponse {
     pub knowledge: Vec<Knowledge>,
@@ -37,26 +65,90 @@ pub struct ListKnowledgeResponse {
 pub struct DevinClient {
     client: Client,
     api_key: String,
+    base_url: String,
 }
 
 impl DevinClient {
-    pub fn new() -> Result<Self, DevinError> {
-        let api_key = env::var("DEVIN_API_KEY").map_err(|_| {
-            DevinError::Config("DEVIN_API_KEY environment variable not set".to_string())
+    /// Creates a client from `DEVIN_API_KEY`. The base URL comes from
+    /// `DEVIN_API_BASE_URL`, then `[adapters.devin] base_url`, then the
+    /// public API.
+    pub fn from_options(options: &DevinOptions) -> Result<Self, DozoError> {
+        let api_key = env::var(API_KEY_ENV).map_err(|_| DozoError::ToolNotConfigured {
+            tool: "devin".to_string(),
+            reason: format!("{} environment variable not set", API_KEY_ENV),
         })?;
+        Ok(Self::with_options(api_key, options))
+    }
+
+    /// Creates a client with `api_key`, configured like [`Self::from_options`].
+    pub fn with_options(api_key: impl Into<String>, options: &DevinOptions) -> Self {
+        let base_url = base_url(env::var(BASE_URL_ENV).ok(), options);
+        Self::new(api_key, base_url).with_timeout(Duration::from_secs(options.timeout_secs))
+    }
 
-        Ok(Self {
+    pub fn new(api_key: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
             client: Client::new(),
-            api_key,
-        })
+            api_key: api_key.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
     }
 
-    pub async fn list_knowledge(&self) -> Result<ListKnowledgeResponse, DevinError> {
-        let url = format!("{}/knowledge", DEVIN_API_BASE_URL);
+    /// Fails requests that take longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        self
+    }
+
+    pub async fn list_knowledge(&self) -> Result<ListKnowledgeResponse, DozoError> {
+        self.send(self.client.get(self.url("/knowledge"))).await
+    }
+
+    pub async fn create_knowledge(
+        &self,
+        request: &KnowledgeRequest,
+    ) -> Result<Knowledge, DozoError> {
+        self.send(self.client.post(self.url("/knowledge")).json(request))
+            .await
+    }
+
+    pub async fn update_knowledge(
+        &self,
+        id: &str,
+        request: &KnowledgeRequest,
+    ) -> Result<Knowledge, DozoError> {
+        let url = self.url(&format!("/knowledge/{}", id));
+        self.send(self.client.put(url).json(request)).await
+    }
+
+    pub async fn delete_knowledge(&self, id: &str) -> Result<(), DozoError> {
+        let url = self.url(&format!("/knowledge/{}", id));
+        self.execute(self.client.delete(url)).await.map(|_| ())
+    }
+
+    pub async fn create_folder(&self, request: &FolderRequest) -> Result<Folder, DozoError> {
+        self.send(
+            self.client
+                .post(self.url("/knowledge/folders"))
+                .json(request),
+        )
+        .await
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}{}", self.base_url, endpoint)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, DozoError> {
+        let text = self.execute(request).await?.text().await?;
+        Ok(serde_json::from_str(&text)?)
+    }
 
-        let response = self
-            .client
-            .get(&url)
+    async fn execute(&self, request: RequestBuilder) -> Result<Response, DozoError> {
+        let response = request
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .send()
@@ -65,32 +157,236 @@ impl DevinClient {
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
-            return Err(DevinError::Api(format!(
+            return Err(DozoError::Api(format!(
                 "API error ({}): {}",
                 status, error_text
             )));
         }
 
-        let knowledge_response = response.json::<ListKnowledgeResponse>().await?;
-
-        Ok(knowledge_response)
+        Ok(response)
     }
 }
 
-#[derive(Debug, thiserror::Error)]
-pub enum DevinError {
-    #[error("API error: {0}")]
-    Api(String),
+fn base_url(from_env: Option<String>, options: &DevinOptions) -> String {
+    from_env
+        .filter(|url| !url.is_empty())
+        .or_else(|| options.base_url.clone())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+}
 
-    #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+/// Returns the folder names from the top level down to each folder, keyed by
+/// folder ID. A folder whose parent is unknown is treated as top level.
+pub fn folder_paths(folders: &[Folder]) -> HashMap<String, Vec<String>> {
+    let by_id: HashMap<&str, &Folder> = folders.iter().map(|f| (f.id.as_str(), f)).collect();
 
-    #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
+    folders
+        .iter()
+        .map(|folder| {
+            let mut path = vec![folder.name.clone()];
+            let mut parent_id = folder.parent_id.as_deref();
+            // A parent cycle would otherwise never end.
+            while let Some(parent) = parent_id.and_then(|id| by_id.get(id)) {
+                if path.len() > folders.len() {
+                    break;
+                }
+                path.push(parent.name.clone());
+                parent_id = parent.parent_id.as_deref();
+            }
+            path.reverse();
+            (folder.id.clone(), path)
+        })
+        .collect()
+}
 
-    #[error("Invalid configuration: {0}")]
-    Config(String),
+/// Marks a rule file as the local copy of a knowledge item, so push updates
+/// that item instead of creating a new one.
+const KNOWLEDGE_ID_PREFIX: &str = "<!-- Devin Knowledge ID:";
+const CREATED_PREFIX: &str = "<!-- Created:";
 
-    #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+pub fn knowledge_id_comment(id: &str) -> String {
+    format!("{} {} -->", KNOWLEDGE_ID_PREFIX, id)
+}
+
+/// Returns the knowledge ID recorded in a rule body, if any.
+pub fn knowledge_id(body: &str) -> Option<&str> {
+    body.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix(KNOWLEDGE_ID_PREFIX)?
+            .strip_suffix("-->")
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    })
+}
+
+/// Removes the ID and creation comments that pull adds to a rule body.
+pub fn strip_knowledge_comments(body: &str) -> String {
+    body.lines()
+        .filter(|line| {
+            let line = line.trim();
+            !line.starts_with(KNOWLEDGE_ID_PREFIX) && !line.starts_with(CREATED_PREFIX)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockDevin;
+    use super::*;
+
+    fn folder(id: &str, name: &str, parent_id: Option<&str>) -> Folder {
+        Folder {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            parent_id: parent_id.map(str::to_string),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_folder_paths() {
+        let folders = vec![
+            folder("f3", "Hooks", Some("f2")),
+            folder("f1", "Frontend", None),
+            folder("f2", "React", Some("f1")),
+            folder("orphan", "Orphan", Some("missing")),
+            folder("a", "A", Some("b")),
+            folder("b", "B", Some("a")),
+        ];
+
+        let paths = folder_paths(&folders);
+        assert_eq!(paths["f3"], vec!["Frontend", "React", "Hooks"]);
+        assert_eq!(paths["f1"], vec!["Frontend"]);
+        assert_eq!(paths["orphan"], vec!["Orphan"]);
+        assert!(paths["a"].len() <= folders.len() + 1);
+    }
+
+    #[test]
+    fn test_base_url_precedence() {
+        let mut options = DevinOptions::default();
+        assert_eq!(base_url(None, &options), DEFAULT_BASE_URL);
+
+        options.base_url = Some("https://devin.internal/v1".to_string());
+        assert_eq!(base_url(None, &options), "https://devin.internal/v1");
+        assert_eq!(
+            base_url(Some("http://localhost:8080".to_string()), &options),
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            DevinClient::new("key", "http://localhost:8080/").url("/knowledge"),
+            "http://localhost:8080/knowledge"
+        );
+    }
+
+    fn request(name: &str, body: &str) -> KnowledgeRequest {
+        KnowledgeRequest {
+            name: name.to_string(),
+            body: body.to_string(),
+            trigger_description: format!("When working on {}", name),
+            parent_folder_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_knowledge_crud() {
+        let server = MockDevin::start().await;
+        server.add_folder("f1", "Frontend", None);
+        server.add_knowledge("note-a", "React", "Use hooks", Some("f1"));
+        let client = server.client();
+
+        let listed = client.list_knowledge().await.unwrap();
+        assert_eq!(listed.knowledge, server.knowledge());
+        assert_eq!(listed.folders, server.folders());
+
+        let created = client
+            .create_knowledge(&request("Testing", "Write tests"))
+            .await
+            .unwrap();
+        assert_eq!(created.name, "Testing");
+
+        let updated = client
+            .update_knowledge(&created.id, &request("Testing", "Write more tests"))
+            .await
+            .unwrap();
+        assert_eq!(updated.body, "Write more tests");
+        assert_eq!(server.received()[2].body["body"], "Write more tests");
+
+        client.delete_knowledge("note-a").await.unwrap();
+        assert_eq!(server.knowledge(), vec![updated]);
+
+        let err = client.delete_knowledge("note-a").await.unwrap_err();
+        assert_eq!(err.code(), "api_error");
+        assert_eq!(
+            server.requests(),
+            vec![
+                "GET /knowledge",
+                "POST /knowledge",
+                format!("PUT /knowledge/{}", created.id).as_str(),
+                "DELETE /knowledge/note-a",
+                "DELETE /knowledge/note-a",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_api_errors() {
+        let server = MockDevin::start().await;
+
+        let err = DevinClient::new("wrong-key", server.url())
+            .list_knowledge()
+            .await
+            .unwrap_err();
+        assert!(matches!(&err, DozoError::Api(message) if message.contains("401")));
+
+        server.fail_with(500, "upstream unavailable");
+        let err = server.client().list_knowledge().await.unwrap_err();
+        assert!(err.to_string().contains("upstream unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_response() {
+        let server = MockDevin::start().await;
+        server.malformed();
+
+        let err = server.client().list_knowledge().await.unwrap_err();
+        assert_eq!(err.code(), "serialization_error");
+    }
+
+    #[tokio::test]
+    async fn test_slow_response_times_out() {
+        let server = MockDevin::start().await;
+        server.delay(Duration::from_millis(500));
+
+        let err = server
+            .client()
+            .with_timeout(Duration::from_millis(50))
+            .list_knowledge()
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "network_error");
+
+        let listed = server
+            .client()
+            .with_timeout(Duration::from_secs(5))
+            .list_knowledge()
+            .await
+            .unwrap();
+        assert!(listed.knowledge.is_empty());
+    }
+
+    #[test]
+    fn test_knowledge_comments() {
+        let body = "<!-- Devin Knowledge ID: note-1 -->\n<!-- Created: 2024-01-01 00:00:00 UTC -->\n\nUse tabs\n";
+
+        assert_eq!(knowledge_id(body), Some("note-1"));
+        assert_eq!(strip_knowledge_comments(body), "Use tabs");
+        assert_eq!(knowledge_id("Use tabs"), None);
+        assert_eq!(
+            knowledge_id(&knowledge_id_comment("note-2")),
+            Some("note-2")
+        );
+    }
 }
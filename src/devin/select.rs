@@ -0,0 +1,248 @@
+//! Rules that choose which Devin knowledge items a pull imports.
+//!
+//! ```toml
+//! [adapters.devin.select]
+//! ids = ["note-123"]              # always imported
+//! folders = ["Frontend"]          # a folder and its subfolders
+//! names = ["(?i)^project"]        # regexes matched against the item name
+//! triggers = ["(?i)rust"]         # regexes matched against the trigger
+//! exclude_folders = ["Archive"]
+//! ```
+//!
+//! An item listed in `ids` is always imported. Otherwise an item matching an
+//! `exclude_*` rule is skipped, and the rest are imported when they match an
+//! include rule, or when there are no include rules at all.
+
+use super::Knowledge;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// `[adapters.devin.select]` options in `dozo.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct SelectOptions {
+    /// Knowledge IDs that are always imported.
+    pub ids: Vec<String>,
+    /// Folder paths such as `Frontend/React`.
+    pub folders: Vec<String>,
+    pub names: Vec<String>,
+    pub triggers: Vec<String>,
+    pub exclude_folders: Vec<String>,
+    pub exclude_names: Vec<String>,
+    pub exclude_triggers: Vec<String>,
+}
+
+/// Whether an item is imported, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Decision {
+    pub keep: bool,
+    pub reason: String,
+}
+
+impl Decision {
+    fn keep(reason: impl Into<String>) -> Self {
+        Self {
+            keep: true,
+            reason: reason.into(),
+        }
+    }
+
+    fn drop(reason: impl Into<String>) -> Self {
+        Self {
+            keep: false,
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Rules {
+    folders: Vec<String>,
+    names: Vec<Regex>,
+    triggers: Vec<Regex>,
+}
+
+impl Rules {
+    fn new(folders: &[String], names: &[String], triggers: &[String]) -> Result<Self> {
+        Ok(Self {
+            folders: folders
+                .iter()
+                .map(|f| f.trim_matches('/').to_string())
+                .collect(),
+            names: compile(names)?,
+            triggers: compile(triggers)?,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.folders.is_empty() && self.names.is_empty() && self.triggers.is_empty()
+    }
+
+    /// Describes the first rule `knowledge` matches.
+    fn matching(&self, knowledge: &Knowledge, folder: &str) -> Option<String> {
+        if let Some(rule) = self.folders.iter().find(|rule| in_folder(folder, rule)) {
+            return Some(format!("folder is under '{}'", rule));
+        }
+        if let Some(regex) = self.names.iter().find(|r| r.is_match(&knowledge.name)) {
+            return Some(format!("name matches /{}/", regex));
+        }
+        self.triggers
+            .iter()
+            .find(|r| r.is_match(&knowledge.trigger_description))
+            .map(|regex| format!("trigger matches /{}/", regex))
+    }
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern)
+                .with_context(|| format!("Invalid [adapters.devin.select] pattern '{}'", pattern))
+        })
+        .collect()
+}
+
+/// True when `folder` is `rule` or one of its subfolders, ignoring case.
+pub fn in_folder(folder: &str, rule: &str) -> bool {
+    let folder = folder.to_lowercase();
+    let rule = rule.to_lowercase();
+    folder == rule || folder.starts_with(&format!("{}/", rule))
+}
+
+/// Compiled selection rules.
+#[derive(Debug, Default)]
+pub struct Selection {
+    all: bool,
+    ids: HashSet<String>,
+    include: Rules,
+    exclude: Rules,
+}
+
+impl Selection {
+    pub fn new(options: &SelectOptions) -> Result<Self> {
+        Ok(Self {
+            all: false,
+            ids: options.ids.iter().cloned().collect(),
+            include: Rules::new(&options.folders, &options.names, &options.triggers)?,
+            exclude: Rules::new(
+                &options.exclude_folders,
+                &options.exclude_names,
+                &options.exclude_triggers,
+            )?,
+        })
+    }
+
+    /// Selects every item, ignoring the rules.
+    pub fn all() -> Self {
+        Self {
+            all: true,
+            ..Self::default()
+        }
+    }
+
+    /// Decides whether to import `knowledge`, which lives in `folder`
+    /// (folder names joined with `/`, empty at the top level).
+    pub fn decide(&self, knowledge: &Knowledge, folder: &str) -> Decision {
+        if self.all {
+            return Decision::keep("--all");
+        }
+        if self.ids.contains(&knowledge.id) {
+            return Decision::keep("ID is listed in ids");
+        }
+        if let Some(rule) = self.exclude.matching(knowledge, folder) {
+            return Decision::drop(format!("excluded: {}", rule));
+        }
+        if self.include.is_empty() {
+            return Decision::keep("no include rules");
+        }
+        match self.include.matching(knowledge, folder) {
+            Some(rule) => Decision::keep(rule),
+            None => Decision::drop("matches no include rule"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn knowledge(id: &str, name: &str, trigger: &str) -> Knowledge {
+        Knowledge {
+            id: id.to_string(),
+            name: name.to_string(),
+            body: String::new(),
+            trigger_description: trigger.to_string(),
+            parent_folder_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_decide() {
+        let selection = Selection::new(&SelectOptions {
+            ids: strings(&["pinned"]),
+            folders: strings(&["Frontend"]),
+            triggers: strings(&["(?i)rust"]),
+            exclude_folders: strings(&["Frontend/Legacy"]),
+            exclude_names: strings(&["^WIP"]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let react = knowledge("a", "React", "When editing components");
+        assert_eq!(
+            selection.decide(&react, "frontend/React"),
+            Decision::keep("folder is under 'Frontend'")
+        );
+        assert!(!selection.decide(&react, "Frontend/Legacy/jQuery").keep);
+        assert!(!selection.decide(&react, "Frontends").keep);
+        assert!(!selection.decide(&react, "").keep);
+
+        let cargo = knowledge("b", "Cargo", "When working on Rust code");
+        assert_eq!(
+            selection.decide(&cargo, ""),
+            Decision::keep("trigger matches /(?i)rust/")
+        );
+        let draft = knowledge("c", "WIP Cargo", "When working on Rust code");
+        assert_eq!(
+            selection.decide(&draft, ""),
+            Decision::drop("excluded: name matches /^WIP/")
+        );
+        let pinned = knowledge("pinned", "WIP notes", "Always");
+        assert!(selection.decide(&pinned, "").keep);
+        assert!(Selection::all().decide(&draft, "").keep);
+    }
+
+    #[test]
+    fn test_no_include_rules_keeps_everything_not_excluded() {
+        let selection = Selection::new(&SelectOptions {
+            exclude_folders: strings(&["Archive"]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let item = knowledge("a", "Old", "Never");
+        assert!(selection.decide(&item, "").keep);
+        assert!(!selection.decide(&item, "Archive").keep);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let err = Selection::new(&SelectOptions {
+            names: strings(&["("]),
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid [adapters.devin.select] pattern '('"));
+    }
+}
@@ -0,0 +1,191 @@
+//! Recording layer for side effects.
+//!
+//! Commands write files, remove files and call external APIs through
+//! [`Effects`]. Every effect is recorded; in dry-run mode nothing is
+//! performed, so the record is the plan.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One recorded side effect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Effect {
+    Write {
+        path: PathBuf,
+        bytes: usize,
+    },
+    #[serde(rename = "delete")]
+    Remove {
+        path: PathBuf,
+    },
+    ApiCall {
+        method: String,
+        endpoint: String,
+    },
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effect::Write { path, bytes } => {
+                write!(f, "write   {} ({} bytes)", path.display(), bytes)
+            }
+            Effect::Remove { path } => write!(f, "delete  {}", path.display()),
+            Effect::ApiCall { method, endpoint } => write!(f, "api     {} {}", method, endpoint),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Effects {
+    dry_run: bool,
+    recorded: Mutex<Vec<Effect>>,
+}
+
+impl Effects {
+    pub fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            recorded: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    fn record(&self, effect: Effect) {
+        self.recorded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(effect);
+    }
+
+    /// Writes `content` to `path`, creating parent directories. The file is
+    /// written to a temporary sibling and renamed into place, so readers
+    /// never see a partial file.
+    pub fn write(&self, path: &Path, content: &str) -> Result<()> {
+        self.record(Effect::Write {
+            path: path.to_path_buf(),
+            bytes: content.len(),
+        });
+        if self.dry_run {
+            return Ok(());
+        }
+
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+            }
+        }
+
+        let file_name = path
+            .file_name()
+            .with_context(|| format!("Invalid file path {}", path.display()))?;
+        let temp_path = path.with_file_name(format!(".{}.dozo-tmp", file_name.to_string_lossy()));
+        std::fs::write(&temp_path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        std::fs::rename(&temp_path, path).map_err(|e| {
+            let _ = std::fs::remove_file(&temp_path);
+            anyhow::Error::new(e).context(format!("Failed to write {}", path.display()))
+        })
+    }
+
+    pub fn remove_file(&self, path: &Path) -> Result<()> {
+        self.record(Effect::Remove {
+            path: path.to_path_buf(),
+        });
+        if self.dry_run {
+            return Ok(());
+        }
+
+        std::fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))
+    }
+
+    /// Removes a directory and everything in it.
+    pub fn remove_dir_all(&self, path: &Path) -> Result<()> {
+        self.record(Effect::Remove {
+            path: path.to_path_buf(),
+        });
+        if self.dry_run {
+            return Ok(());
+        }
+
+        std::fs::remove_dir_all(path)
+            .with_context(|| format!("Failed to remove {}", path.display()))
+    }
+
+    /// Records an API request. Callers must not send requests that change
+    /// remote state when [`is_dry_run`](Self::is_dry_run) is true.
+    pub fn api_call(&self, method: &str, endpoint: &str) {
+        self.record(Effect::ApiCall {
+            method: method.to_string(),
+            endpoint: endpoint.to_string(),
+        });
+    }
+
+    /// Returns the recorded effects and clears the record.
+    pub fn take(&self) -> Vec<Effect> {
+        std::mem::take(&mut *self.recorded.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+/// Prints the effects a dry run would have performed.
+pub fn print_dry_run(effects: &[Effect]) {
+    if effects.is_empty() {
+        println!("📝 Dry run: nothing to do");
+        return;
+    }
+
+    println!("📝 Dry run: nothing was changed. Planned actions:");
+    for effect in effects {
+        println!("   {}", effect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dry_run_records_without_touching_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let existing = temp_dir.path().join("keep.md");
+        fs::write(&existing, "Keep").unwrap();
+
+        let effects = Effects::new(true);
+        effects
+            .write(&temp_dir.path().join("new/a.md"), "Hello")
+            .unwrap();
+        effects.remove_file(&existing).unwrap();
+        effects.api_call("GET", "/knowledge");
+
+        assert!(!temp_dir.path().join("new").exists());
+        assert!(existing.exists());
+        let recorded = effects.take();
+        assert_eq!(recorded.len(), 3);
+        assert_eq!(recorded[2].to_string(), "api     GET /knowledge");
+        assert!(effects.take().is_empty());
+    }
+
+    #[test]
+    fn test_effects_are_performed() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("new/a.md");
+
+        let effects = Effects::new(false);
+        effects.write(&path, "Hello").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Hello");
+
+        effects.remove_file(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(effects.take().len(), 2);
+    }
+}
@@ -0,0 +1,138 @@
+//! Errors that callers can branch on.
+//!
+//! Every variant has a stable [`code`](DozoError::code) and a remediation
+//! [`hint`](DozoError::hint). Commands still return `anyhow::Error` and add
+//! context; [`DozoError::find`] recovers the typed error from the chain.
+
+use std::path::PathBuf;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DozoError {
+    #[error("Configuration directory '{0}' not found")]
+    ConfigNotFound(String),
+
+    /// A command would overwrite existing files.
+    #[error("{path} already contains {count} file(s)")]
+    FileExists { path: String, count: usize },
+
+    /// The tool has no configuration to pull, or lacks credentials.
+    #[error("{tool} is not configured: {reason}")]
+    ToolNotConfigured { tool: String, reason: String },
+
+    #[error("Invalid front-matter: {0}")]
+    InvalidFrontMatter(serde_yaml::Error),
+
+    #[error("Unknown tool '{name}'. Available tools: {available}")]
+    UnknownTool { name: String, available: String },
+
+    #[error("{tool} does not support {operation}")]
+    Unsupported { tool: String, operation: String },
+
+    #[error("Another dozo run is in progress (pid {pid})")]
+    Locked { pid: String, path: PathBuf },
+
+    #[error("{failed} target(s) failed")]
+    TargetsFailed { succeeded: usize, failed: usize },
+
+    #[error("{count} generated file(s) are out of date with {config_dir}")]
+    OutOfDate { count: usize, config_dir: String },
+
+    #[error("No Devin knowledge item with ID '{0}'")]
+    KnowledgeNotFound(String),
+
+    #[error("API error: {0}")]
+    Api(String),
+
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl DozoError {
+    /// Stable identifier for the kind of error, for scripts and reports.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DozoError::ConfigNotFound(_) => "config_not_found",
+            DozoError::FileExists { .. } => "file_exists",
+            DozoError::ToolNotConfigured { .. } => "tool_not_configured",
+            DozoError::InvalidFrontMatter(_) => "invalid_front_matter",
+            DozoError::UnknownTool { .. } => "unknown_tool",
+            DozoError::Unsupported { .. } => "unsupported",
+            DozoError::Locked { .. } => "locked",
+            DozoError::TargetsFailed { .. } => "targets_failed",
+            DozoError::OutOfDate { .. } => "out_of_date",
+            DozoError::KnowledgeNotFound(_) => "knowledge_not_found",
+            DozoError::Api(_) => "api_error",
+            DozoError::Network(_) => "network_error",
+            DozoError::Serialization(_) => "serialization_error",
+        }
+    }
+
+    /// What the user can do about the error.
+    pub fn hint(&self) -> String {
+        match self {
+            DozoError::ConfigNotFound(_) => {
+                "Run `dozo init` to create it, or pass `--config <DIR>`".to_string()
+            }
+            DozoError::FileExists { .. } => {
+                "Use `dozo pull --merge` to add to the existing files, or choose another `--config` directory"
+                    .to_string()
+            }
+            DozoError::ToolNotConfigured { tool, .. } if tool == "devin" => {
+                "Set DEVIN_API_KEY to an API key from the Devin settings".to_string()
+            }
+            DozoError::ToolNotConfigured { tool, .. } => {
+                format!("Create the {} configuration first, or pull from another tool", tool)
+            }
+            DozoError::InvalidFrontMatter(_) => {
+                "Fix the YAML between the `---` lines at the top of the file".to_string()
+            }
+            DozoError::UnknownTool { available, .. } => format!("Use one of: {}", available),
+            DozoError::Unsupported { .. } => {
+                "Run `dozo --help` to see what each tool supports".to_string()
+            }
+            DozoError::Locked { path, .. } => format!(
+                "Wait for the other run to finish. If none is running, remove {}",
+                path.display()
+            ),
+            DozoError::TargetsFailed { .. } => {
+                "Fix the errors above and run the command again".to_string()
+            }
+            DozoError::OutOfDate { .. } => "Run `dozo push` and commit the result".to_string(),
+            DozoError::KnowledgeNotFound(_) => {
+                "Run `dozo devin list` to see the available IDs".to_string()
+            }
+            DozoError::Api(_) => "Check that the API key is valid and has access".to_string(),
+            DozoError::Network(_) => "Check your network connection and try again".to_string(),
+            DozoError::Serialization(_) => {
+                "The response was not in the expected format; try again or report a bug"
+                    .to_string()
+            }
+        }
+    }
+
+    /// Finds the first `DozoError` in an error's chain.
+    pub fn find(error: &anyhow::Error) -> Option<&DozoError> {
+        error.chain().find_map(|e| e.downcast_ref::<DozoError>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_find_through_context() {
+        let error = Err::<(), _>(DozoError::ConfigNotFound(".agentic-coding".to_string()))
+            .context("Failed to push")
+            .unwrap_err();
+
+        let found = DozoError::find(&error).unwrap();
+        assert_eq!(found.code(), "config_not_found");
+        assert!(found.hint().contains("dozo init"));
+        assert!(DozoError::find(&anyhow::anyhow!("plain")).is_none());
+    }
+}
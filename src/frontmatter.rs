@@ -0,0 +1,230 @@
+//! Canonical YAML front-matter for rule files in the config directory.
+//!
+//! A rule file may start with a `---` delimited YAML block:
+//!
+//! ```markdown
+//! ---
+//! title: React Rules
+//! description: Conventions for React components
+//! globs: ["src/**/*.tsx"]
+//! always_apply: false
+//! tags: [frontend]
+//! ---
+//! Use function components.
+//! ```
+//!
+//! Push adapters translate [`RuleMetadata`] into each tool's native format and
+//! pull adapters translate native metadata back into it.
+
+use crate::error::DozoError;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::Path;
+
+const DELIMITER: &str = "---";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// File patterns the rule applies to. Accepts a YAML list or a
+    /// comma-separated string.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_globs"
+    )]
+    pub globs: Vec<String>,
+
+    #[serde(
+        default,
+        alias = "alwaysApply",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub always_apply: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl RuleMetadata {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A rule file split into its metadata and markdown body.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleDocument {
+    pub metadata: RuleMetadata,
+    pub body: String,
+}
+
+impl RuleDocument {
+    pub fn new(metadata: RuleMetadata, body: impl Into<String>) -> Self {
+        Self {
+            metadata,
+            body: body.into(),
+        }
+    }
+
+    /// Parses a rule file. Content without front-matter yields empty metadata
+    /// and the unchanged content as body.
+    pub fn parse(content: &str) -> Result<Self> {
+        let Some((yaml, body)) = split_front_matter(content) else {
+            return Ok(Self::new(RuleMetadata::default(), content));
+        };
+
+        let metadata = if yaml.trim().is_empty() {
+            RuleMetadata::default()
+        } else {
+            serde_yaml::from_str(yaml).map_err(DozoError::InvalidFrontMatter)?
+        };
+
+        Ok(Self::new(metadata, body))
+    }
+
+    /// Reads and parses a rule file from disk.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Renders the document with a canonical front-matter block. The block is
+    /// omitted when there is no metadata.
+    pub fn render(&self) -> String {
+        if self.metadata.is_empty() {
+            return self.body.clone();
+        }
+
+        let yaml = serde_yaml::to_string(&self.metadata).unwrap_or_default();
+        format!("{}\n{}{}\n{}", DELIMITER, yaml, DELIMITER, self.body)
+    }
+}
+
+/// Splits `content` into the raw front-matter block and the remaining body.
+/// Returns `None` when the content does not start with a `---` line.
+pub fn split_front_matter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let first_line_end = rest.find('\n')?;
+    if rest[..first_line_end].trim_end() != DELIMITER {
+        return None;
+    }
+
+    let block = &rest[first_line_end + 1..];
+    let mut offset = 0;
+    for line in block.split_inclusive('\n') {
+        if line.trim_end() == DELIMITER {
+            let yaml = &block[..offset];
+            let body = &block[offset + line.len()..];
+            return Some((yaml, body));
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+fn deserialize_globs<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Globs {
+        List(Vec<String>),
+        Single(String),
+    }
+
+    Ok(match Option::<Globs>::deserialize(deserializer)? {
+        None => Vec::new(),
+        Some(Globs::List(list)) => list,
+        Some(Globs::Single(s)) => s
+            .split(',')
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        let doc = RuleDocument::parse("# Title\n\nBody").unwrap();
+        assert!(doc.metadata.is_empty());
+        assert_eq!(doc.body, "# Title\n\nBody");
+    }
+
+    #[test]
+    fn test_parse_front_matter() {
+        let content = "---\ntitle: React Rules\ndescription: React conventions\nglobs: [\"src/**/*.tsx\", \"src/**/*.jsx\"]\nalways_apply: false\ntags: [frontend]\n---\nUse hooks.\n";
+        let doc = RuleDocument::parse(content).unwrap();
+
+        assert_eq!(doc.metadata.title.as_deref(), Some("React Rules"));
+        assert_eq!(
+            doc.metadata.description.as_deref(),
+            Some("React conventions")
+        );
+        assert_eq!(doc.metadata.globs, vec!["src/**/*.tsx", "src/**/*.jsx"]);
+        assert_eq!(doc.metadata.always_apply, Some(false));
+        assert_eq!(doc.metadata.tags, vec!["frontend"]);
+        assert_eq!(doc.body, "Use hooks.\n");
+    }
+
+    #[test]
+    fn test_parse_comma_separated_globs() {
+        let doc = RuleDocument::parse("---\nglobs: \"*.rs, *.toml\"\n---\nBody").unwrap();
+        assert_eq!(doc.metadata.globs, vec!["*.rs", "*.toml"]);
+    }
+
+    #[test]
+    fn test_parse_accepts_camel_case_always_apply() {
+        let doc = RuleDocument::parse("---\nalwaysApply: true\n---\nBody").unwrap();
+        assert_eq!(doc.metadata.always_apply, Some(true));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        let result = RuleDocument::parse("---\ntitel: Typo\n---\nBody");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_unterminated_front_matter_is_body() {
+        let content = "---\ntitle: Missing end\nBody";
+        let doc = RuleDocument::parse(content).unwrap();
+        assert!(doc.metadata.is_empty());
+        assert_eq!(doc.body, content);
+    }
+
+    #[test]
+    fn test_render_round_trip() {
+        let metadata = RuleMetadata {
+            title: Some("Security".to_string()),
+            description: Some("Input validation rules".to_string()),
+            globs: vec!["src/**/*.rs".to_string()],
+            always_apply: Some(false),
+            tags: vec!["backend".to_string()],
+        };
+        let doc = RuleDocument::new(metadata, "Validate input.\n");
+
+        let rendered = doc.render();
+        assert!(rendered.starts_with("---\ntitle: Security\n"));
+        assert_eq!(RuleDocument::parse(&rendered).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_render_without_metadata() {
+        let doc = RuleDocument::new(RuleMetadata::default(), "Just body");
+        assert_eq!(doc.render(), "Just body");
+    }
+}
@@ -1,3 +1,18 @@
+pub mod adapters;
+pub mod backup;
 pub mod cli;
 pub mod commands;
+pub mod config;
 pub mod devin;
+pub mod effects;
+pub mod error;
+pub mod frontmatter;
+pub mod lock;
+pub mod manifest;
+pub mod mdc;
+pub mod output;
+pub mod project;
+pub mod region;
+pub mod report;
+pub mod sync;
+pub mod template;
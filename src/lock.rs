@@ -0,0 +1,65 @@
+//! Lock file that keeps two dozo runs from writing at the same time.
+
+use crate::error::DozoError;
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+pub const LOCK_FILE_NAME: &str = ".dozo-push.lock";
+
+/// Held while a run writes generated files. The lock file is removed when
+/// the guard is dropped.
+#[derive(Debug)]
+pub struct PushLock {
+    path: PathBuf,
+}
+
+impl PushLock {
+    /// Creates the lock file in `config_dir`, failing if another run holds it.
+    pub fn acquire(config_dir: &Path) -> Result<Self> {
+        let path = config_dir.join(LOCK_FILE_NAME);
+        let mut file = match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                let owner = std::fs::read_to_string(&path).unwrap_or_default();
+                return Err(DozoError::Locked {
+                    pid: owner.trim().to_string(),
+                    path,
+                }
+                .into());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create {}", path.display()))
+            }
+        };
+        write!(file, "{}", std::process::id())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for PushLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_lock_is_exclusive_until_dropped() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let lock = PushLock::acquire(temp_dir.path()).unwrap();
+        let err = PushLock::acquire(temp_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("Another dozo run is in progress"));
+
+        drop(lock);
+        assert!(!temp_dir.path().join(LOCK_FILE_NAME).exists());
+        PushLock::acquire(temp_dir.path()).unwrap();
+    }
+}
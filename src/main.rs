@@ -1,21 +1,51 @@
-mod cli;
-mod commands;
-mod devin;
-
 use clap::Parser;
-use cli::Cli;
+use dozo::adapters::AdapterRegistry;
+use dozo::cli::Cli;
+use dozo::commands;
+use dozo::config::DozoConfig;
+use dozo::error::DozoError;
+use dozo::report::{OutputFormat, Reporter};
+use std::path::Path;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    let config_dir = cli.config_dir();
+    let registry = AdapterRegistry::with_builtin();
+
+    let config = match load_config(&cli, &registry) {
+        Ok(config) => config,
+        Err(e) => {
+            Reporter::new(cli.format, cli.command.name()).finish(Some(&e))?;
+            fail(&e);
+        }
+    };
 
-    if cli.verbose {
+    if cli.verbose && cli.format != OutputFormat::Json {
         println!("🔧 Running in verbose mode");
-        println!("📁 Using config directory: {}", config_dir);
+        println!("📁 Using config directory: {}", config.config_dir());
     }
 
-    commands::execute_command(cli.command, &config_dir, cli.verbose).await?;
+    if let Err(e) =
+        commands::execute_command(&registry, &config, cli.command, cli.format, cli.verbose).await
+    {
+        fail(&e);
+    }
 
     Ok(())
 }
+
+fn load_config(cli: &Cli, registry: &AdapterRegistry) -> anyhow::Result<DozoConfig> {
+    let mut config = DozoConfig::load(Path::new(""))?;
+    config.validate(registry)?;
+    cli.apply_overrides(&mut config);
+    Ok(config)
+}
+
+/// Prints the error and its hint and exits with the matching code.
+fn fail(error: &anyhow::Error) -> ! {
+    eprintln!("Error: {:?}", error);
+    if let Some(typed) = DozoError::find(error) {
+        eprintln!("\nhint: {}", typed.hint());
+    }
+    std::process::exit(commands::exit_code(error));
+}
@@ -0,0 +1,138 @@
+//! Record of the files dozo generated.
+//!
+//! The manifest lives in the config directory and maps every generated path
+//! to the adapter that owns it and a hash of the generated content. Push uses
+//! it to update or prune only dozo's own files and to notice when one of them
+//! was edited by hand.
+
+use crate::effects::Effects;
+use crate::output::OutputKind;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_FILE_NAME: &str = ".dozo-lock.json";
+const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Name of the adapter that generated the file.
+    pub target: String,
+    pub kind: OutputKind,
+    /// Hash of the generated content (the region content for regions).
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    /// Generated files keyed by their project-relative path.
+    #[serde(default)]
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+impl Manifest {
+    pub fn path(config_dir: &Path) -> PathBuf {
+        config_dir.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Loads the manifest, returning an empty one when none has been written.
+    pub fn load(config_dir: &Path) -> Result<Self> {
+        let path = Self::path(config_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let manifest: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        if manifest.version != MANIFEST_VERSION {
+            anyhow::bail!(
+                "Unsupported manifest version {} in {}",
+                manifest.version,
+                path.display()
+            );
+        }
+        Ok(manifest)
+    }
+
+    pub fn save(&self, config_dir: &Path, effects: &Effects) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        effects.write(&Self::path(config_dir), &(content + "\n"))
+    }
+
+    /// Entries owned by one adapter.
+    pub fn entries_for<'a>(
+        &'a self,
+        target: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a ManifestEntry)> + 'a {
+        self.files.iter().filter(move |(_, e)| e.target == target)
+    }
+
+    /// Names of every adapter that owns a file.
+    pub fn targets(&self) -> BTreeSet<&str> {
+        self.files.values().map(|e| e.target.as_str()).collect()
+    }
+}
+
+/// Hex-encoded SHA-256 of `content`.
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_missing_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = Manifest::load(temp_dir.path()).unwrap();
+        assert!(manifest.files.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::default();
+        manifest.files.insert(
+            "CLAUDE.md".to_string(),
+            ManifestEntry {
+                target: "claude".to_string(),
+                kind: OutputKind::Region,
+                hash: content_hash("x"),
+            },
+        );
+        manifest.save(temp_dir.path(), &Effects::default()).unwrap();
+
+        let loaded = Manifest::load(temp_dir.path()).unwrap();
+        assert_eq!(loaded, manifest);
+        assert_eq!(loaded.entries_for("claude").count(), 1);
+        assert_eq!(loaded.entries_for("cursor").count(), 0);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
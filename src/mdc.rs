@@ -0,0 +1,217 @@
+//! Cursor's `.mdc` rule format.
+//!
+//! MDC files carry a front-matter block with exactly three keys. Unlike YAML,
+//! `globs` is a bare comma-separated list and values are never quoted:
+//!
+//! ```text
+//! ---
+//! description: React conventions
+//! globs: src/**/*.tsx,src/**/*.jsx
+//! alwaysApply: false
+//! ---
+//! ```
+
+use crate::frontmatter::{split_front_matter, RuleDocument, RuleMetadata};
+
+/// Renders a rule as an `.mdc` file.
+///
+/// Rules without explicit `always_apply` default to always applying unless
+/// they are scoped by globs or describe when they should be requested, which
+/// mirrors how Cursor classifies rules created in its own UI.
+pub fn render(document: &RuleDocument) -> String {
+    let metadata = &document.metadata;
+
+    let description = metadata
+        .description
+        .as_deref()
+        .or(metadata.title.as_deref())
+        .map(single_line)
+        .unwrap_or_default();
+    let globs = metadata.globs.join(",");
+    let always_apply = metadata
+        .always_apply
+        .unwrap_or(metadata.globs.is_empty() && metadata.description.is_none());
+
+    format!(
+        "---\ndescription: {}\nglobs: {}\nalwaysApply: {}\n---\n{}",
+        description, globs, always_apply, document.body
+    )
+}
+
+/// Parses an `.mdc` file into dozo's rule representation.
+///
+/// Cursor treats a rule without a header, or with `alwaysApply` missing, as
+/// a manual rule, so `always_apply` is always set explicitly to keep that
+/// meaning when the rule is pushed back.
+pub fn parse(content: &str) -> RuleDocument {
+    let Some((header, body)) = split_front_matter(content) else {
+        let metadata = RuleMetadata {
+            always_apply: Some(false),
+            ..Default::default()
+        };
+        return RuleDocument::new(metadata, content);
+    };
+
+    let mut metadata = RuleMetadata {
+        always_apply: Some(false),
+        ..Default::default()
+    };
+
+    for line in header.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = unquote(value.trim());
+
+        match key.trim() {
+            "description" if !value.is_empty() => metadata.description = Some(value.to_string()),
+            "globs" => metadata.globs = parse_globs(value),
+            "alwaysApply" => metadata.always_apply = Some(value.eq_ignore_ascii_case("true")),
+            _ => {}
+        }
+    }
+
+    RuleDocument::new(metadata, body)
+}
+
+fn parse_globs(value: &str) -> Vec<String> {
+    let value = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+
+    value
+        .split(',')
+        .map(|glob| unquote(glob.trim()).to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+fn single_line(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontmatter::RuleMetadata;
+
+    #[test]
+    fn test_render_defaults_to_always_apply() {
+        let document = RuleDocument::new(RuleMetadata::default(), "# Rules\nBody\n");
+        assert_eq!(
+            render(&document),
+            "---\ndescription: \nglobs: \nalwaysApply: true\n---\n# Rules\nBody\n"
+        );
+    }
+
+    #[test]
+    fn test_render_globs_attach_automatically() {
+        let metadata = RuleMetadata {
+            globs: vec!["src/**/*.tsx".to_string(), "*.css".to_string()],
+            ..Default::default()
+        };
+        let rendered = render(&RuleDocument::new(metadata, "Body"));
+        assert!(rendered.contains("\nglobs: src/**/*.tsx,*.css\n"));
+        assert!(rendered.contains("\nalwaysApply: false\n"));
+    }
+
+    #[test]
+    fn test_render_description_is_agent_requested() {
+        let metadata = RuleMetadata {
+            description: Some("Use when writing\nSQL migrations".to_string()),
+            ..Default::default()
+        };
+        let rendered = render(&RuleDocument::new(metadata, "Body"));
+        assert!(rendered.contains("\ndescription: Use when writing SQL migrations\n"));
+        assert!(rendered.contains("\nalwaysApply: false\n"));
+    }
+
+    #[test]
+    fn test_render_title_fallback_keeps_always_apply() {
+        let metadata = RuleMetadata {
+            title: Some("Security".to_string()),
+            ..Default::default()
+        };
+        let rendered = render(&RuleDocument::new(metadata, "Body"));
+        assert!(rendered.contains("\ndescription: Security\n"));
+        assert!(rendered.contains("\nalwaysApply: true\n"));
+    }
+
+    #[test]
+    fn test_render_explicit_always_apply_wins() {
+        let metadata = RuleMetadata {
+            globs: vec!["*.rs".to_string()],
+            always_apply: Some(true),
+            ..Default::default()
+        };
+        let rendered = render(&RuleDocument::new(metadata, "Body"));
+        assert!(rendered.contains("\nalwaysApply: true\n"));
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let document = parse(
+            "---\ndescription: React rules\nglobs: src/**/*.tsx,*.css\nalwaysApply: false\n---\nUse hooks\n",
+        );
+        assert_eq!(
+            document.metadata.description.as_deref(),
+            Some("React rules")
+        );
+        assert_eq!(document.metadata.globs, vec!["src/**/*.tsx", "*.css"]);
+        assert_eq!(document.metadata.always_apply, Some(false));
+        assert_eq!(document.body, "Use hooks\n");
+    }
+
+    #[test]
+    fn test_parse_quoted_and_list_values() {
+        let document =
+            parse("---\ndescription: \"Quoted: value\"\nglobs: [\"*.rs\", '*.toml']\n---\nBody");
+        assert_eq!(
+            document.metadata.description.as_deref(),
+            Some("Quoted: value")
+        );
+        assert_eq!(document.metadata.globs, vec!["*.rs", "*.toml"]);
+    }
+
+    #[test]
+    fn test_parse_empty_values() {
+        let document = parse("---\ndescription:\nglobs:\nalwaysApply: true\n---\nBody");
+        assert!(document.metadata.description.is_none());
+        assert!(document.metadata.globs.is_empty());
+        assert_eq!(document.metadata.always_apply, Some(true));
+    }
+
+    #[test]
+    fn test_parse_without_header_is_manual_rule() {
+        let document = parse("# Rule\nBody");
+        assert_eq!(document.metadata.always_apply, Some(false));
+        assert_eq!(document.body, "# Rule\nBody");
+    }
+
+    #[test]
+    fn test_round_trip_preserves_semantics() {
+        for content in [
+            "---\ndescription: React rules\nglobs: src/**/*.tsx,*.css\nalwaysApply: false\n---\nUse hooks\n",
+            "---\ndescription: \nglobs: \nalwaysApply: true\n---\nAlways\n",
+            "---\ndescription: Use for migrations\nglobs: \nalwaysApply: false\n---\nRequested\n",
+            "---\ndescription: \nglobs: \nalwaysApply: false\n---\nManual\n",
+        ] {
+            let document = parse(content);
+            let canonical = RuleDocument::parse(&document.render()).unwrap();
+            assert_eq!(render(&canonical), content);
+        }
+    }
+}
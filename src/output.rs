@@ -0,0 +1,41 @@
+//! Files rendered by adapters before they are written to the project.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// How dozo owns an output file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputKind {
+    /// dozo owns the whole file.
+    File,
+    /// dozo owns only the marked region inside a shared file.
+    Region,
+}
+
+/// A file an adapter wants to exist after a push.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// Path relative to the project root.
+    pub path: PathBuf,
+    pub content: String,
+    pub kind: OutputKind,
+}
+
+impl Output {
+    pub fn file(path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+            kind: OutputKind::File,
+        }
+    }
+
+    pub fn region(path: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+            kind: OutputKind::Region,
+        }
+    }
+}
@@ -0,0 +1,428 @@
+//! Detects how a project is built and tested so generated agent memory can
+//! describe the real commands instead of generic placeholders.
+
+use std::path::Path;
+
+/// A shell command an agent can run in the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCommand {
+    /// What the command does, e.g. "Build" or "Run tests".
+    pub label: String,
+    pub command: String,
+    /// The file the command was detected from, e.g. `Cargo.toml`.
+    pub source: String,
+}
+
+impl ProjectCommand {
+    fn new(label: &str, command: impl Into<String>, source: &str) -> Self {
+        Self {
+            label: label.to_string(),
+            command: command.into(),
+            source: source.to_string(),
+        }
+    }
+}
+
+/// Package scripts and make/just targets worth documenting, with labels.
+const WELL_KNOWN_TASKS: &[(&str, &str)] = &[
+    ("install", "Install dependencies"),
+    ("build", "Build"),
+    ("test", "Run tests"),
+    ("check", "Check"),
+    ("lint", "Lint"),
+    ("typecheck", "Type check"),
+    ("fmt", "Format"),
+    ("format", "Format"),
+    ("dev", "Start development server"),
+    ("start", "Start"),
+    ("run", "Run"),
+];
+
+/// Inspects the project root for known manifests and returns the commands
+/// they imply, in a stable order.
+pub fn detect_commands(project_root: &Path) -> Vec<ProjectCommand> {
+    let mut commands = Vec::new();
+    commands.extend(detect_cargo(project_root));
+    commands.extend(detect_node(project_root));
+    commands.extend(detect_python(project_root));
+    commands.extend(detect_go(project_root));
+    commands.extend(detect_jvm(project_root));
+    commands.extend(detect_make(project_root));
+    commands.extend(detect_just(project_root));
+    commands
+}
+
+/// Renders a `## Commands` section, or an empty string when nothing was
+/// detected.
+pub fn commands_section(commands: &[ProjectCommand]) -> String {
+    if commands.is_empty() {
+        return String::new();
+    }
+
+    let multiple_sources = commands.iter().any(|c| c.source != commands[0].source);
+    let entries: Vec<String> = commands
+        .iter()
+        .map(|c| {
+            if multiple_sources {
+                format!("# {} ({})\n{}", c.label, c.source, c.command)
+            } else {
+                format!("# {}\n{}", c.label, c.command)
+            }
+        })
+        .collect();
+
+    format!("\n## Commands\n```bash\n{}\n```\n", entries.join("\n\n"))
+}
+
+fn read(project_root: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(project_root.join(file)).ok()
+}
+
+fn detect_cargo(root: &Path) -> Vec<ProjectCommand> {
+    const SOURCE: &str = "Cargo.toml";
+    let Some(content) = read(root, SOURCE) else {
+        return Vec::new();
+    };
+
+    let is_workspace = content
+        .parse::<toml::Table>()
+        .map(|t| t.contains_key("workspace"))
+        .unwrap_or(false);
+    let scope = if is_workspace { " --workspace" } else { "" };
+
+    vec![
+        ProjectCommand::new("Build", format!("cargo build{}", scope), SOURCE),
+        ProjectCommand::new("Run tests", format!("cargo test{}", scope), SOURCE),
+        ProjectCommand::new(
+            "Lint",
+            format!("cargo clippy{} --all-targets -- -D warnings", scope),
+            SOURCE,
+        ),
+        ProjectCommand::new("Format", "cargo fmt --all", SOURCE),
+    ]
+}
+
+fn node_package_manager(root: &Path, package: &serde_json::Value) -> &'static str {
+    if let Some(declared) = package.get("packageManager").and_then(|v| v.as_str()) {
+        for manager in ["pnpm", "yarn", "bun", "npm"] {
+            if declared.starts_with(manager) {
+                return manager;
+            }
+        }
+    }
+
+    if root.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if root.join("yarn.lock").exists() {
+        "yarn"
+    } else if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        "bun"
+    } else {
+        "npm"
+    }
+}
+
+fn detect_node(root: &Path) -> Vec<ProjectCommand> {
+    const SOURCE: &str = "package.json";
+    let Some(package) = read(root, SOURCE).and_then(|c| serde_json::from_str(&c).ok()) else {
+        return Vec::new();
+    };
+    let package: serde_json::Value = package;
+    let manager = node_package_manager(root, &package);
+
+    let mut commands = vec![ProjectCommand::new(
+        "Install dependencies",
+        format!("{} install", manager),
+        SOURCE,
+    )];
+
+    let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) else {
+        return commands;
+    };
+
+    for (script, label) in WELL_KNOWN_TASKS {
+        if !scripts.contains_key(*script) {
+            continue;
+        }
+        let command = match (manager, *script) {
+            ("npm", "test" | "start") => format!("npm {}", script),
+            ("npm" | "bun", _) => format!("{} run {}", manager, script),
+            _ => format!("{} {}", manager, script),
+        };
+        commands.push(ProjectCommand::new(label, command, SOURCE));
+    }
+
+    commands
+}
+
+fn detect_python(root: &Path) -> Vec<ProjectCommand> {
+    const SOURCE: &str = "pyproject.toml";
+    let Some(pyproject) = read(root, SOURCE).and_then(|c| c.parse::<toml::Table>().ok()) else {
+        return Vec::new();
+    };
+    let tool = pyproject.get("tool").and_then(|t| t.as_table());
+    let has_tool = |name: &str| tool.is_some_and(|t| t.contains_key(name));
+
+    let (install, runner) = if root.join("uv.lock").exists() || has_tool("uv") {
+        ("uv sync", "uv run ")
+    } else if root.join("poetry.lock").exists() || has_tool("poetry") {
+        ("poetry install", "poetry run ")
+    } else if root.join("pdm.lock").exists() || has_tool("pdm") {
+        ("pdm install", "pdm run ")
+    } else {
+        ("pip install -e .", "")
+    };
+
+    let mut commands = vec![
+        ProjectCommand::new("Install dependencies", install, SOURCE),
+        ProjectCommand::new("Run tests", format!("{}pytest", runner), SOURCE),
+    ];
+    if has_tool("ruff") {
+        commands.push(ProjectCommand::new(
+            "Lint",
+            format!("{}ruff check .", runner),
+            SOURCE,
+        ));
+        commands.push(ProjectCommand::new(
+            "Format",
+            format!("{}ruff format .", runner),
+            SOURCE,
+        ));
+    }
+    if has_tool("mypy") {
+        commands.push(ProjectCommand::new(
+            "Type check",
+            format!("{}mypy .", runner),
+            SOURCE,
+        ));
+    }
+    commands
+}
+
+fn detect_go(root: &Path) -> Vec<ProjectCommand> {
+    const SOURCE: &str = "go.mod";
+    if !root.join(SOURCE).exists() {
+        return Vec::new();
+    }
+
+    vec![
+        ProjectCommand::new("Build", "go build ./...", SOURCE),
+        ProjectCommand::new("Run tests", "go test ./...", SOURCE),
+        ProjectCommand::new("Lint", "go vet ./...", SOURCE),
+        ProjectCommand::new("Format", "gofmt -w .", SOURCE),
+    ]
+}
+
+fn detect_jvm(root: &Path) -> Vec<ProjectCommand> {
+    for (source, wrapper, fallback) in [
+        ("build.gradle.kts", "gradlew", "gradle"),
+        ("build.gradle", "gradlew", "gradle"),
+    ] {
+        if root.join(source).exists() {
+            let gradle = if root.join(wrapper).exists() {
+                "./gradlew".to_string()
+            } else {
+                fallback.to_string()
+            };
+            return vec![
+                ProjectCommand::new("Build", format!("{} build", gradle), source),
+                ProjectCommand::new("Run tests", format!("{} test", gradle), source),
+            ];
+        }
+    }
+
+    const MAVEN: &str = "pom.xml";
+    if root.join(MAVEN).exists() {
+        let mvn = if root.join("mvnw").exists() {
+            "./mvnw"
+        } else {
+            "mvn"
+        };
+        return vec![
+            ProjectCommand::new("Build", format!("{} package", mvn), MAVEN),
+            ProjectCommand::new("Run tests", format!("{} test", mvn), MAVEN),
+        ];
+    }
+
+    Vec::new()
+}
+
+fn detect_make(root: &Path) -> Vec<ProjectCommand> {
+    const SOURCE: &str = "Makefile";
+    let Some(content) = read(root, SOURCE) else {
+        return Vec::new();
+    };
+
+    let targets: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace) && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (names, rest) = line.split_once(':')?;
+            // Skip variable assignments such as `CC := gcc`.
+            if rest.starts_with('=') || names.contains('=') {
+                return None;
+            }
+            Some(names.split_whitespace())
+        })
+        .flatten()
+        .collect();
+
+    well_known_tasks(&targets, "make", SOURCE)
+}
+
+fn detect_just(root: &Path) -> Vec<ProjectCommand> {
+    let Some((source, content)) = ["justfile", "Justfile", ".justfile"]
+        .iter()
+        .find_map(|name| read(root, name).map(|c| (*name, c)))
+    else {
+        return Vec::new();
+    };
+
+    let recipes: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace) && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (head, rest) = line.split_once(':')?;
+            if rest.starts_with('=') || head.starts_with("set ") || head.starts_with("alias ") {
+                return None;
+            }
+            let name = head.trim_start_matches('@').split_whitespace().next()?;
+            Some(name)
+        })
+        .collect();
+
+    well_known_tasks(&recipes, "just", source)
+}
+
+fn well_known_tasks(available: &[&str], runner: &str, source: &str) -> Vec<ProjectCommand> {
+    WELL_KNOWN_TASKS
+        .iter()
+        .filter(|(task, _)| available.contains(task))
+        .map(|(task, label)| ProjectCommand::new(label, format!("{} {}", runner, task), source))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commands_in(files: &[(&str, &str)]) -> Vec<String> {
+        let temp_dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            fs::write(temp_dir.path().join(name), content).unwrap();
+        }
+        detect_commands(temp_dir.path())
+            .into_iter()
+            .map(|c| c.command)
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_nothing() {
+        assert!(commands_in(&[]).is_empty());
+        assert_eq!(commands_section(&[]), "");
+    }
+
+    #[test]
+    fn test_detect_cargo() {
+        let commands = commands_in(&[("Cargo.toml", "[package]\nname = \"x\"\n")]);
+        assert_eq!(commands[0], "cargo build");
+        assert!(commands.contains(&"cargo test".to_string()));
+
+        let commands = commands_in(&[("Cargo.toml", "[workspace]\nmembers = []\n")]);
+        assert!(commands.contains(&"cargo test --workspace".to_string()));
+    }
+
+    #[test]
+    fn test_detect_node_package_manager() {
+        let package =
+            r#"{"scripts": {"build": "tsc", "test": "vitest", "dev": "vite", "deploy": "x"}}"#;
+
+        let commands = commands_in(&[("package.json", package)]);
+        assert_eq!(
+            commands,
+            vec!["npm install", "npm run build", "npm test", "npm run dev"]
+        );
+
+        let commands = commands_in(&[("package.json", package), ("pnpm-lock.yaml", "")]);
+        assert_eq!(
+            commands,
+            vec!["pnpm install", "pnpm build", "pnpm test", "pnpm dev"]
+        );
+
+        let commands = commands_in(&[("package.json", package), ("yarn.lock", "")]);
+        assert!(commands.contains(&"yarn test".to_string()));
+
+        let commands = commands_in(&[("package.json", package), ("bun.lockb", "")]);
+        assert!(commands.contains(&"bun run test".to_string()));
+    }
+
+    #[test]
+    fn test_detect_node_declared_package_manager() {
+        let package = r#"{"packageManager": "yarn@4.1.0", "scripts": {"lint": "eslint ."}}"#;
+        let commands = commands_in(&[("package.json", package)]);
+        assert_eq!(commands, vec!["yarn install", "yarn lint"]);
+    }
+
+    #[test]
+    fn test_detect_python() {
+        let commands = commands_in(&[
+            ("pyproject.toml", "[project]\nname = \"x\"\n[tool.ruff]\n"),
+            ("uv.lock", ""),
+        ]);
+        assert_eq!(
+            commands,
+            vec![
+                "uv sync",
+                "uv run pytest",
+                "uv run ruff check .",
+                "uv run ruff format ."
+            ]
+        );
+
+        let commands = commands_in(&[("pyproject.toml", "[tool.poetry]\nname = \"x\"\n")]);
+        assert_eq!(commands, vec!["poetry install", "poetry run pytest"]);
+    }
+
+    #[test]
+    fn test_detect_go() {
+        let commands = commands_in(&[("go.mod", "module example.com/x\n")]);
+        assert!(commands.contains(&"go test ./...".to_string()));
+    }
+
+    #[test]
+    fn test_detect_make_targets() {
+        let makefile = "CC := gcc\n.PHONY: build test\nbuild: deps\n\tgo build\ntest:\n\tgo test\nrelease:\n\t./release.sh\n";
+        let commands = commands_in(&[("Makefile", makefile)]);
+        assert_eq!(commands, vec!["make build", "make test"]);
+    }
+
+    #[test]
+    fn test_detect_just_recipes() {
+        let justfile = "set shell := [\"bash\", \"-c\"]\nversion := \"1\"\n\n# Run tests\ntest *args:\n    cargo test {{args}}\n@lint:\n    cargo clippy\n";
+        let commands = commands_in(&[("justfile", justfile)]);
+        assert_eq!(commands, vec!["just test", "just lint"]);
+    }
+
+    #[test]
+    fn test_commands_section() {
+        let commands = vec![
+            ProjectCommand::new("Build", "cargo build", "Cargo.toml"),
+            ProjectCommand::new("Run tests", "cargo test", "Cargo.toml"),
+        ];
+        assert_eq!(
+            commands_section(&commands),
+            "\n## Commands\n```bash\n# Build\ncargo build\n\n# Run tests\ncargo test\n```\n"
+        );
+
+        let commands = vec![
+            ProjectCommand::new("Build", "cargo build", "Cargo.toml"),
+            ProjectCommand::new("Run tests", "make test", "Makefile"),
+        ];
+        let section = commands_section(&commands);
+        assert!(section.contains("# Build (Cargo.toml)\ncargo build"));
+        assert!(section.contains("# Run tests (Makefile)\nmake test"));
+    }
+}
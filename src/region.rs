@@ -0,0 +1,172 @@
+//! Managed regions inside files that dozo shares with hand-written content.
+//!
+//! Generated content is wrapped in marker comments. Later pushes replace only
+//! the text between the markers, so notes written above or below them are
+//! kept.
+
+use anyhow::Result;
+
+pub const BEGIN_MARKER: &str = "<!-- dozo:begin -->";
+pub const END_MARKER: &str = "<!-- dozo:end -->";
+
+/// Wraps generated content in region markers.
+pub fn wrap(generated: &str) -> String {
+    format!(
+        "{}\n{}\n{}\n",
+        BEGIN_MARKER,
+        generated.trim_end_matches('\n'),
+        END_MARKER
+    )
+}
+
+/// Byte range of the region (markers included) within `content`, with the
+/// trailing newline after the end marker if present.
+fn find(content: &str) -> Result<Option<(usize, usize)>> {
+    let begin = content.find(BEGIN_MARKER);
+    let end = content.find(END_MARKER);
+
+    match (begin, end) {
+        (None, None) => Ok(None),
+        (Some(begin), Some(end)) if begin < end => {
+            let rest = &content[end + END_MARKER.len()..];
+            if rest.contains(BEGIN_MARKER) || rest.contains(END_MARKER) {
+                anyhow::bail!("Found more than one dozo region; expected exactly one");
+            }
+            let mut end = end + END_MARKER.len();
+            if content[end..].starts_with('\n') {
+                end += 1;
+            }
+            Ok(Some((begin, end)))
+        }
+        _ => anyhow::bail!(
+            "Malformed dozo region: expected '{}' followed by '{}'",
+            BEGIN_MARKER,
+            END_MARKER
+        ),
+    }
+}
+
+/// Produces the new file content for `generated`.
+///
+/// A missing file becomes just the region. An existing file keeps everything
+/// outside the markers; if it has no markers yet the region is appended.
+pub fn apply(existing: Option<&str>, generated: &str) -> Result<String> {
+    let region = wrap(generated);
+    let Some(existing) = existing else {
+        return Ok(region);
+    };
+
+    match find(existing)? {
+        Some((begin, end)) => Ok(format!(
+            "{}{}{}",
+            &existing[..begin],
+            region,
+            &existing[end..]
+        )),
+        None if existing.trim().is_empty() => Ok(region),
+        None => {
+            let separator = if existing.ends_with("\n\n") {
+                ""
+            } else if existing.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            Ok(format!("{}{}{}", existing, separator, region))
+        }
+    }
+}
+
+/// Removes the region, returning only the hand-written content.
+pub fn strip(content: &str) -> Result<String> {
+    Ok(match find(content)? {
+        Some((begin, end)) => {
+            let before = content[..begin].trim_end_matches('\n');
+            let after = content[end..].trim_start_matches('\n');
+            match (before.is_empty(), after.is_empty()) {
+                (true, _) => after.to_string(),
+                (false, true) => format!("{}\n", before),
+                (false, false) => format!("{}\n\n{}", before, after),
+            }
+        }
+        None => content.to_string(),
+    })
+}
+
+/// Returns the content between the markers, if the file has a region.
+pub fn extract(content: &str) -> Result<Option<&str>> {
+    Ok(find(content)?.map(|(begin, end)| {
+        let inner = &content[begin + BEGIN_MARKER.len()..end];
+        let inner = inner.trim_end_matches('\n');
+        let inner = inner.strip_suffix(END_MARKER).unwrap_or(inner);
+        inner.trim_start_matches('\n').trim_end_matches('\n')
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_new_file() {
+        let content = apply(None, "Generated\n").unwrap();
+        assert_eq!(
+            content,
+            "<!-- dozo:begin -->\nGenerated\n<!-- dozo:end -->\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_appends_to_unmanaged_file() {
+        let content = apply(Some("# My notes\nKeep me"), "Generated").unwrap();
+        assert_eq!(
+            content,
+            "# My notes\nKeep me\n\n<!-- dozo:begin -->\nGenerated\n<!-- dozo:end -->\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_replaces_only_region() {
+        let existing =
+            "# Notes above\n\n<!-- dozo:begin -->\nOld\n<!-- dozo:end -->\n\n# Notes below\n";
+        let content = apply(Some(existing), "New").unwrap();
+        assert_eq!(
+            content,
+            "# Notes above\n\n<!-- dozo:begin -->\nNew\n<!-- dozo:end -->\n\n# Notes below\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_is_idempotent() {
+        let first = apply(Some("Notes\n"), "Generated").unwrap();
+        let second = apply(Some(&first), "Generated").unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_apply_rejects_malformed_regions() {
+        assert!(apply(Some("<!-- dozo:begin -->\nNo end"), "x").is_err());
+        assert!(apply(Some("<!-- dozo:end -->\n<!-- dozo:begin -->"), "x").is_err());
+        let twice = format!("{}{}", wrap("a"), wrap("b"));
+        assert!(apply(Some(&twice), "x").is_err());
+    }
+
+    #[test]
+    fn test_strip() {
+        let content = apply(Some("# Notes\n"), "Generated").unwrap();
+        assert_eq!(strip(&content).unwrap(), "# Notes\n");
+        assert_eq!(strip(&wrap("Generated")).unwrap(), "");
+        assert_eq!(
+            strip(&format!("Above\n\n{}\nBelow\n", wrap("x"))).unwrap(),
+            "Above\n\nBelow\n"
+        );
+        assert_eq!(strip("Unmanaged").unwrap(), "Unmanaged");
+    }
+
+    #[test]
+    fn test_extract() {
+        let content = format!("Notes\n\n{}Tail", wrap("Line 1\nLine 2"));
+        assert_eq!(extract(&content).unwrap(), Some("Line 1\nLine 2"));
+        assert_eq!(extract("No region").unwrap(), None);
+    }
+}
@@ -0,0 +1,306 @@
+//! Command reports.
+//!
+//! Commands describe what they did through a [`Reporter`]. In text mode it
+//! prints the usual progress lines; with `--format json` those lines are
+//! suppressed and one [`Report`] is printed when the command finishes.
+
+use crate::effects::{print_dry_run, Effect};
+use crate::error::DozoError;
+use crate::sync::SyncOutcome;
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Mutex;
+
+/// Output format selected with the global `--format` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    /// GitHub Actions workflow commands. Only `dozo check` uses them; other
+    /// commands print text.
+    Github,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetResult {
+    Ok,
+    Failed,
+    /// The target does not support the command.
+    Skipped,
+    /// A fail-fast run stopped before this target was written.
+    NotRun,
+}
+
+/// What a command did for one target.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetReport {
+    pub target: String,
+    pub result: TargetResult,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub written: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unchanged: Vec<String>,
+    /// Files left alone because they were edited by hand or not owned by dozo.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deleted: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Command-specific fields.
+    #[serde(flatten)]
+    pub details: Map<String, Value>,
+}
+
+impl TargetReport {
+    pub fn new(target: &str, result: TargetResult) -> Self {
+        Self {
+            target: target.to_string(),
+            result,
+            written: Vec::new(),
+            unchanged: Vec::new(),
+            skipped: Vec::new(),
+            deleted: Vec::new(),
+            warnings: Vec::new(),
+            error: None,
+            details: Map::new(),
+        }
+    }
+
+    pub fn from_outcome(target: &str, outcome: &SyncOutcome) -> Self {
+        Self {
+            written: outcome.written.clone(),
+            unchanged: outcome.unchanged.clone(),
+            skipped: outcome.skipped.clone(),
+            deleted: outcome.deleted.clone(),
+            warnings: outcome.warnings.clone(),
+            ..Self::new(target, TargetResult::Ok)
+        }
+    }
+
+    pub fn failed(target: &str, error: &anyhow::Error) -> Self {
+        Self {
+            error: Some(format!("{:#}", error)),
+            ..Self::new(target, TargetResult::Failed)
+        }
+    }
+
+    pub fn with_detail(mut self, key: &str, value: impl Serialize) -> Self {
+        self.details.insert(key.to_string(), to_value(value));
+        self
+    }
+}
+
+/// An item a pull imported, such as a Devin knowledge entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceItem {
+    /// ID in the source tool, when it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    /// File the item was written to.
+    pub path: String,
+}
+
+/// The structured result of one command.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub command: String,
+    pub ok: bool,
+    pub targets: Vec<TargetReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<SourceItem>,
+    /// Effects a dry run would have performed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub planned: Vec<Effect>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+    /// Stable [`DozoError::code`] of the error the command failed with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Command-specific fields.
+    #[serde(flatten)]
+    pub details: Map<String, Value>,
+}
+
+#[derive(Debug)]
+pub struct Reporter {
+    format: OutputFormat,
+    command: String,
+    report: Mutex<Report>,
+}
+
+impl Default for Reporter {
+    fn default() -> Self {
+        Self::new(OutputFormat::Text, "")
+    }
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat, command: &str) -> Self {
+        Self {
+            format,
+            command: command.to_string(),
+            report: Mutex::new(Report::default()),
+        }
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Report)) {
+        f(&mut self.report.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+
+    /// Prints a progress line in text mode.
+    pub fn say(&self, line: impl fmt::Display) {
+        if !self.is_json() {
+            println!("{}", line);
+        }
+    }
+
+    pub fn target(&self, target: TargetReport) {
+        self.update(|report| report.targets.push(target));
+    }
+
+    pub fn source(&self, item: SourceItem) {
+        self.update(|report| report.sources.push(item));
+    }
+
+    /// Prints a problem with a file `target` generated: a line in text mode,
+    /// or an `::error` annotation with `--format github`.
+    pub fn file_error(&self, path: &str, target: &str, message: &str) {
+        match self.format {
+            OutputFormat::Text => println!("❌ {} ({}): {}", path, target, message),
+            OutputFormat::Json => {}
+            OutputFormat::Github => println!(
+                "::error file={},title=dozo {} ({})::{}",
+                path, self.command, target, message
+            ),
+        }
+    }
+
+    /// Reports a warning that does not belong to a single target.
+    pub fn warning(&self, message: impl Into<String>) {
+        let message = message.into();
+        self.say(format_args!("⚠️  {}", message));
+        self.update(|report| report.warnings.push(message));
+    }
+
+    pub fn detail(&self, key: &str, value: impl Serialize) {
+        let value = to_value(value);
+        self.update(|report| {
+            report.details.insert(key.to_string(), value);
+        });
+    }
+
+    /// Reports the effects a dry run would have performed.
+    pub fn planned(&self, effects: Vec<Effect>) {
+        if !self.is_json() {
+            print_dry_run(&effects);
+        }
+        self.update(|report| report.planned.extend(effects));
+    }
+
+    /// Returns the report collected so far and starts a new one.
+    pub fn take(&self, error: Option<&anyhow::Error>) -> Report {
+        let mut report = Report::default();
+        self.update(|current| report = std::mem::take(current));
+        report.command = self.command.clone();
+        if let Some(error) = error {
+            report.errors.push(format!("{:#}", error));
+            if let Some(typed) = DozoError::find(error) {
+                report.code = Some(typed.code().to_string());
+                report.hint = Some(typed.hint());
+            }
+        }
+        report.ok = report.errors.is_empty()
+            && report
+                .targets
+                .iter()
+                .all(|t| t.result != TargetResult::Failed);
+        report
+    }
+
+    /// Prints the report collected so far when the format is JSON.
+    pub fn finish(&self, error: Option<&anyhow::Error>) -> Result<()> {
+        let report = self.take(error);
+        if self.is_json() {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Ok(())
+    }
+}
+
+fn to_value(value: impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_report_json() {
+        let reporter = Reporter::new(OutputFormat::Json, "push");
+        reporter.target(TargetReport::from_outcome(
+            "cursor",
+            &SyncOutcome {
+                written: vec![".cursorrules".to_string()],
+                ..Default::default()
+            },
+        ));
+        reporter.target(TargetReport::new("devin", TargetResult::Skipped));
+        reporter.planned(vec![Effect::Remove {
+            path: PathBuf::from("a.md"),
+        }]);
+
+        let report = reporter.take(None);
+        assert!(report.ok);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "command": "push",
+                "ok": true,
+                "targets": [
+                    {"target": "cursor", "result": "ok", "written": [".cursorrules"]},
+                    {"target": "devin", "result": "skipped"},
+                ],
+                "planned": [{"action": "delete", "path": "a.md"}],
+                "warnings": [],
+                "errors": [],
+            })
+        );
+
+        reporter.target(TargetReport::failed("claude", &anyhow::anyhow!("boom")));
+        let report = reporter.take(None);
+        assert!(!report.ok);
+        assert_eq!(report.targets.len(), 1);
+    }
+
+    #[test]
+    fn test_report_error_code() {
+        let reporter = Reporter::new(OutputFormat::Json, "push");
+        let error = anyhow::Error::new(DozoError::ConfigNotFound("rules".to_string()));
+
+        let report = reporter.take(Some(&error));
+        assert!(!report.ok);
+        assert_eq!(report.code.as_deref(), Some("config_not_found"));
+        assert!(report.hint.unwrap().contains("dozo init"));
+    }
+}
@@ -0,0 +1,702 @@
+//! Plans and applies adapter outputs against the project and the manifest.
+//!
+//! Planning is pure: it reads the current files and decides what would change
+//! without writing anything. Applying writes the plan and records the
+//! generated files in the manifest.
+
+use crate::effects::Effects;
+use crate::manifest::{content_hash, Manifest, ManifestEntry};
+use crate::output::{Output, OutputKind};
+use crate::region;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+    /// Left alone, e.g. because it was edited by hand.
+    Skip,
+}
+
+/// A planned change to one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the project root.
+    pub path: String,
+    pub kind: ChangeKind,
+    pub output_kind: OutputKind,
+    /// Current file content, if the file exists.
+    pub before: Option<String>,
+    /// File content after the change; `None` when the file is deleted or
+    /// left alone.
+    pub after: Option<String>,
+    /// Hash to record in the manifest afterwards; `None` drops the entry.
+    pub hash: Option<String>,
+}
+
+/// Everything a push would do for one adapter.
+#[derive(Debug, Clone, Default)]
+pub struct TargetPlan {
+    pub target: String,
+    pub changes: Vec<FileChange>,
+    pub warnings: Vec<String>,
+}
+
+impl TargetPlan {
+    /// Changes that modify the disk.
+    pub fn pending(&self) -> impl Iterator<Item = &FileChange> {
+        self.changes.iter().filter(|c| {
+            matches!(
+                c.kind,
+                ChangeKind::Create | ChangeKind::Update | ChangeKind::Delete
+            )
+        })
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.pending().next().is_some()
+    }
+}
+
+impl FileChange {
+    /// Unified diff between the current file and the planned content, empty
+    /// when the file is not changed.
+    pub fn unified_diff(&self) -> String {
+        if !matches!(
+            self.kind,
+            ChangeKind::Create | ChangeKind::Update | ChangeKind::Delete
+        ) {
+            return String::new();
+        }
+
+        let before = self.before.as_deref().unwrap_or_default();
+        let after = self.after.as_deref().unwrap_or_default();
+        let old_header = match self.kind {
+            ChangeKind::Create => "/dev/null".to_string(),
+            _ => format!("a/{}", self.path),
+        };
+        let new_header = match self.after {
+            Some(_) => format!("b/{}", self.path),
+            None => "/dev/null".to_string(),
+        };
+
+        TextDiff::from_lines(before, after)
+            .unified_diff()
+            .context_radius(3)
+            .header(&old_header, &new_header)
+            .to_string()
+    }
+}
+
+/// Paths touched by applying a plan.
+#[derive(Debug, Clone, Default)]
+pub struct SyncOutcome {
+    pub written: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub deleted: Vec<String>,
+    pub skipped: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl SyncOutcome {
+    /// Files that hold dozo's generated content after the push.
+    pub fn generated(&self) -> Vec<String> {
+        self.written
+            .iter()
+            .chain(&self.unchanged)
+            .cloned()
+            .collect()
+    }
+}
+
+pub fn path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn read_existing(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Hash of dozo's part of a file as it is on disk.
+fn owned_hash(kind: OutputKind, content: &str) -> Result<Option<String>> {
+    Ok(match kind {
+        OutputKind::File => Some(content_hash(content)),
+        OutputKind::Region => region::extract(content)?.map(content_hash),
+    })
+}
+
+fn generated_hash(output: &Output) -> String {
+    match output.kind {
+        OutputKind::File => content_hash(&output.content),
+        OutputKind::Region => content_hash(output.content.trim_matches('\n')),
+    }
+}
+
+/// Decides how to bring `target`'s files in line with `outputs`.
+///
+/// Files recorded in the manifest are updated or pruned. Files that were
+/// edited by hand since the last push, and existing files dozo does not own,
+/// are left alone with a warning unless `force` is set.
+pub fn plan_target(
+    project_root: &Path,
+    manifest: &Manifest,
+    target: &str,
+    outputs: Vec<Output>,
+    force: bool,
+) -> Result<TargetPlan> {
+    let mut plan = TargetPlan {
+        target: target.to_string(),
+        ..Default::default()
+    };
+    let mut rendered = BTreeSet::new();
+
+    for output in outputs {
+        let key = path_key(&output.path);
+        rendered.insert(key.clone());
+
+        let before = read_existing(&project_root.join(&output.path))?;
+        let hash = generated_hash(&output);
+        let recorded = manifest.files.get(&key).filter(|e| e.target == target);
+        // A region in a file dozo owns outright (e.g. one `dozo init`
+        // imported) replaces the whole file.
+        let owned_kind = match recorded {
+            Some(entry) if entry.kind == OutputKind::File => OutputKind::File,
+            _ => output.kind,
+        };
+
+        let after = match (output.kind, owned_kind) {
+            (OutputKind::File, _) => output.content.clone(),
+            (OutputKind::Region, OutputKind::File) => region::apply(None, &output.content)
+                .with_context(|| format!("Failed to update {}", key))?,
+            (OutputKind::Region, OutputKind::Region) => {
+                region::apply(before.as_deref(), &output.content)
+                    .with_context(|| format!("Failed to update {}", key))?
+            }
+        };
+
+        let current_hash = match &before {
+            Some(content) => owned_hash(owned_kind, content)
+                .with_context(|| format!("Failed to parse {}", key))?,
+            None => None,
+        };
+
+        let mut change = FileChange {
+            path: key.clone(),
+            kind: ChangeKind::Update,
+            output_kind: output.kind,
+            before: before.clone(),
+            after: Some(after.clone()),
+            hash: Some(hash.clone()),
+        };
+
+        if before.as_deref() == Some(after.as_str()) {
+            change.kind = ChangeKind::Unchanged;
+            change.after = None;
+        } else if before.is_none() {
+            change.kind = ChangeKind::Create;
+        } else if let Some(entry) = recorded {
+            let edited = current_hash.as_deref() != Some(entry.hash.as_str());
+            if edited && !force {
+                plan.warnings.push(format!(
+                    "{} was edited by hand since the last push; leaving it alone (use --force to overwrite)",
+                    key
+                ));
+                change.kind = ChangeKind::Skip;
+                change.after = None;
+                change.hash = Some(entry.hash.clone());
+            }
+        } else if output.kind == OutputKind::File && !force {
+            plan.warnings.push(format!(
+                "{} already exists and was not generated by dozo; leaving it alone (use --force to overwrite)",
+                key
+            ));
+            change.kind = ChangeKind::Skip;
+            change.after = None;
+            change.hash = None;
+        }
+
+        plan.changes.push(change);
+    }
+
+    for (key, entry) in manifest.entries_for(target) {
+        if rendered.contains(key) {
+            continue;
+        }
+
+        let path = project_root.join(key);
+        let Some(before) = read_existing(&path)? else {
+            continue;
+        };
+        let current_hash =
+            owned_hash(entry.kind, &before).with_context(|| format!("Failed to parse {}", key))?;
+        let edited = current_hash.as_deref() != Some(entry.hash.as_str());
+
+        let mut change = FileChange {
+            path: key.clone(),
+            kind: ChangeKind::Delete,
+            output_kind: entry.kind,
+            before: Some(before.clone()),
+            after: None,
+            hash: None,
+        };
+
+        if edited && !force && current_hash.is_some() {
+            plan.warnings.push(format!(
+                "{} is no longer generated but was edited by hand; keeping it and no longer tracking it",
+                key
+            ));
+            change.kind = ChangeKind::Skip;
+        } else if entry.kind == OutputKind::Region {
+            let remaining =
+                region::strip(&before).with_context(|| format!("Failed to parse {}", key))?;
+            if !remaining.trim().is_empty() {
+                change.kind = ChangeKind::Update;
+                change.after = Some(remaining);
+            }
+        }
+
+        plan.changes.push(change);
+    }
+
+    Ok(plan)
+}
+
+/// Writes a plan to disk and records the result in the manifest.
+pub fn apply_plan(
+    project_root: &Path,
+    plan: &TargetPlan,
+    manifest: &mut Manifest,
+    effects: &Effects,
+) -> Result<SyncOutcome> {
+    let mut outcomes = apply_plans(project_root, std::slice::from_ref(plan), manifest, effects)?;
+    Ok(outcomes.remove(0))
+}
+
+/// Writes several plans as one transaction. If any change fails, the files
+/// already changed are restored and the manifest is left untouched.
+pub fn apply_plans(
+    project_root: &Path,
+    plans: &[TargetPlan],
+    manifest: &mut Manifest,
+    effects: &Effects,
+) -> Result<Vec<SyncOutcome>> {
+    let mut staged = manifest.clone();
+    let mut applied = Vec::new();
+    let mut outcomes = Vec::new();
+
+    for plan in plans {
+        match apply_changes(project_root, plan, &mut staged, effects, &mut applied) {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(e) => {
+                rollback(project_root, &applied, effects);
+                return Err(e.context(format!(
+                    "Push to {} failed; all changes were rolled back",
+                    plan.target
+                )));
+            }
+        }
+    }
+
+    *manifest = staged;
+    Ok(outcomes)
+}
+
+fn apply_changes<'a>(
+    project_root: &Path,
+    plan: &'a TargetPlan,
+    manifest: &mut Manifest,
+    effects: &Effects,
+    applied: &mut Vec<&'a FileChange>,
+) -> Result<SyncOutcome> {
+    let mut outcome = SyncOutcome {
+        warnings: plan.warnings.clone(),
+        ..Default::default()
+    };
+
+    for change in &plan.changes {
+        let path = project_root.join(&change.path);
+        match change.kind {
+            ChangeKind::Create | ChangeKind::Update => {
+                effects.write(&path, change.after.as_deref().unwrap_or_default())?;
+                applied.push(change);
+                outcome.written.push(path.display().to_string());
+            }
+            ChangeKind::Delete => {
+                effects.remove_file(&path)?;
+                applied.push(change);
+                if !effects.is_dry_run() {
+                    remove_empty_parents(project_root, &path);
+                }
+                outcome.deleted.push(path.display().to_string());
+            }
+            ChangeKind::Unchanged => outcome.unchanged.push(path.display().to_string()),
+            ChangeKind::Skip => outcome.skipped.push(path.display().to_string()),
+        }
+
+        match &change.hash {
+            Some(hash) => {
+                manifest.files.insert(
+                    change.path.clone(),
+                    ManifestEntry {
+                        target: plan.target.clone(),
+                        kind: change.output_kind,
+                        hash: hash.clone(),
+                    },
+                );
+            }
+            None => {
+                manifest.files.remove(&change.path);
+            }
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Restores the files touched by `applied`, newest first.
+fn rollback(project_root: &Path, applied: &[&FileChange], effects: &Effects) {
+    for change in applied.iter().rev() {
+        let path = project_root.join(&change.path);
+        let result = match &change.before {
+            Some(before) => effects.write(&path, before),
+            None => effects.remove_file(&path).map(|()| {
+                if !effects.is_dry_run() {
+                    remove_empty_parents(project_root, &path);
+                }
+            }),
+        };
+        if let Err(e) = result {
+            eprintln!("⚠️  Failed to roll back {}: {:#}", path.display(), e);
+        }
+    }
+}
+
+/// Removes directories left empty by a deletion, up to the project root.
+pub fn remove_empty_parents(project_root: &Path, path: &Path) {
+    let mut current: Option<PathBuf> = path.parent().map(Path::to_path_buf);
+    while let Some(dir) = current {
+        if dir.as_os_str().is_empty() || dir == project_root {
+            break;
+        }
+        if std::fs::remove_dir(&dir).is_err() {
+            break;
+        }
+        current = dir.parent().map(Path::to_path_buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn sync(
+        root: &Path,
+        manifest: &mut Manifest,
+        outputs: Vec<Output>,
+        force: bool,
+    ) -> SyncOutcome {
+        let plan = plan_target(root, manifest, "test", outputs, force).unwrap();
+        apply_plan(root, &plan, manifest, &Effects::default()).unwrap()
+    }
+
+    #[test]
+    fn test_apply_plans_rolls_back_on_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let mut manifest = Manifest::default();
+        sync(
+            root,
+            &mut manifest,
+            vec![Output::file("a.md", "old")],
+            false,
+        );
+        let before = manifest.clone();
+
+        let plans = vec![
+            plan_target(
+                root,
+                &manifest,
+                "test",
+                vec![Output::file("a.md", "new"), Output::file("new/b.md", "B")],
+                false,
+            )
+            .unwrap(),
+            plan_target(
+                root,
+                &manifest,
+                "other",
+                vec![Output::file("blocker/c.md", "C")],
+                true,
+            )
+            .unwrap(),
+        ];
+        // Something else claims the path between planning and writing.
+        fs::write(root.join("blocker"), "not a directory").unwrap();
+
+        let err = apply_plans(root, &plans, &mut manifest, &Effects::default()).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("rolled back"));
+        assert_eq!(fs::read_to_string(root.join("a.md")).unwrap(), "old");
+        assert!(!root.join("new").exists());
+        assert_eq!(manifest, before);
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::default();
+        sync(
+            temp_dir.path(),
+            &mut manifest,
+            vec![Output::file("a.md", "one\ntwo\n")],
+            false,
+        );
+
+        let plan = plan_target(
+            temp_dir.path(),
+            &manifest,
+            "test",
+            vec![
+                Output::file("a.md", "one\nthree\n"),
+                Output::file("b.md", "new\n"),
+            ],
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.changes[0].unified_diff(),
+            "--- a/a.md\n+++ b/a.md\n@@ -1,2 +1,2 @@\n one\n-two\n+three\n"
+        );
+        assert_eq!(
+            plan.changes[1].unified_diff(),
+            "--- /dev/null\n+++ b/b.md\n@@ -0,0 +1 @@\n+new\n"
+        );
+    }
+
+    #[test]
+    fn test_creates_and_records_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::default();
+
+        let outcome = sync(
+            temp_dir.path(),
+            &mut manifest,
+            vec![Output::file("rules/a.mdc", "A")],
+            false,
+        );
+
+        assert_eq!(outcome.written.len(), 1);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("rules/a.mdc")).unwrap(),
+            "A"
+        );
+        assert_eq!(manifest.files["rules/a.mdc"].hash, content_hash("A"));
+        assert_eq!(manifest.files["rules/a.mdc"].target, "test");
+    }
+
+    #[test]
+    fn test_second_push_is_unchanged() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut manifest = Manifest::default();
+        let outputs = vec![Output::file("a.mdc", "A"), Output::region("CLAUDE.md", "R")];
+
+        sync(temp_dir.path(), &mut manifest, outputs.clone(), false);
+        let plan = plan_target(temp_dir.path(), &manifest, "test", outputs, false).unwrap();
+
+        assert!(!plan.has_changes());
+        assert!(plan.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_prunes_only_owned_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let mut manifest = Manifest::default();
+
+        sync(
+            root,
+            &mut manifest,
+            vec![
+                Output::file("rules/old/a.mdc", "A"),
+                Output::file("rules/b.mdc", "B"),
+            ],
+            false,
+        );
+        fs::write(root.join("rules/teammate.mdc"), "Hand written").unwrap();
+
+        let outcome = sync(
+            root,
+            &mut manifest,
+            vec![Output::file("rules/b.mdc", "B")],
+            false,
+        );
+
+        assert_eq!(outcome.deleted.len(), 1);
+        assert!(!root.join("rules/old").exists());
+        assert!(root.join("rules/b.mdc").exists());
+        assert!(root.join("rules/teammate.mdc").exists());
+        assert!(!manifest.files.contains_key("rules/old/a.mdc"));
+    }
+
+    #[test]
+    fn test_warns_about_hand_edited_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let mut manifest = Manifest::default();
+
+        sync(root, &mut manifest, vec![Output::file("a.mdc", "A")], false);
+        fs::write(root.join("a.mdc"), "Edited").unwrap();
+
+        let outcome = sync(
+            root,
+            &mut manifest,
+            vec![Output::file("a.mdc", "A2")],
+            false,
+        );
+        assert_eq!(outcome.skipped.len(), 1);
+        assert!(outcome.warnings[0].contains("edited by hand"));
+        assert_eq!(fs::read_to_string(root.join("a.mdc")).unwrap(), "Edited");
+        // The old hash is kept so the drift is reported again next time.
+        assert_eq!(manifest.files["a.mdc"].hash, content_hash("A"));
+
+        let outcome = sync(root, &mut manifest, vec![Output::file("a.mdc", "A2")], true);
+        assert_eq!(outcome.written.len(), 1);
+        assert_eq!(fs::read_to_string(root.join("a.mdc")).unwrap(), "A2");
+    }
+
+    #[test]
+    fn test_keeps_hand_edited_files_that_are_no_longer_generated() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let mut manifest = Manifest::default();
+
+        sync(root, &mut manifest, vec![Output::file("a.mdc", "A")], false);
+        fs::write(root.join("a.mdc"), "Edited").unwrap();
+
+        let outcome = sync(root, &mut manifest, vec![], false);
+        assert!(outcome.deleted.is_empty());
+        assert!(root.join("a.mdc").exists());
+        assert!(manifest.files.is_empty());
+    }
+
+    #[test]
+    fn test_does_not_overwrite_unmanaged_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("a.mdc"), "Teammate").unwrap();
+        let mut manifest = Manifest::default();
+
+        let outcome = sync(root, &mut manifest, vec![Output::file("a.mdc", "A")], false);
+        assert_eq!(outcome.skipped.len(), 1);
+        assert_eq!(fs::read_to_string(root.join("a.mdc")).unwrap(), "Teammate");
+        assert!(manifest.files.is_empty());
+
+        sync(root, &mut manifest, vec![Output::file("a.mdc", "A")], true);
+        assert_eq!(fs::read_to_string(root.join("a.mdc")).unwrap(), "A");
+        assert!(manifest.files.contains_key("a.mdc"));
+    }
+
+    #[test]
+    fn test_region_drift_and_removal() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("CLAUDE.md"), "# Notes\n").unwrap();
+        let mut manifest = Manifest::default();
+
+        sync(
+            root,
+            &mut manifest,
+            vec![Output::region("CLAUDE.md", "Gen")],
+            false,
+        );
+        let content = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        fs::write(root.join("CLAUDE.md"), content.replace("Gen", "Tweaked")).unwrap();
+
+        let outcome = sync(
+            root,
+            &mut manifest,
+            vec![Output::region("CLAUDE.md", "Gen2")],
+            false,
+        );
+        assert_eq!(outcome.skipped.len(), 1);
+
+        // Edits outside the region are not drift.
+        let outcome = sync(
+            root,
+            &mut manifest,
+            vec![Output::region("CLAUDE.md", "Gen2")],
+            true,
+        );
+        assert_eq!(outcome.written.len(), 1);
+        let content = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        fs::write(root.join("CLAUDE.md"), format!("{}More notes\n", content)).unwrap();
+        let plan = plan_target(
+            root,
+            &manifest,
+            "test",
+            vec![Output::region("CLAUDE.md", "Gen2")],
+            false,
+        )
+        .unwrap();
+        assert!(plan.warnings.is_empty());
+
+        // A region that is no longer rendered is removed, keeping the notes.
+        sync(root, &mut manifest, vec![], false);
+        let content = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert!(!content.contains("dozo:begin"));
+        assert!(content.contains("# Notes"));
+        assert!(content.contains("More notes"));
+    }
+
+    #[test]
+    fn test_region_in_owned_file_replaces_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("CLAUDE.md"), "# Notes\n").unwrap();
+        let mut manifest = Manifest::default();
+        manifest.files.insert(
+            "CLAUDE.md".to_string(),
+            ManifestEntry {
+                target: "test".to_string(),
+                kind: OutputKind::File,
+                hash: content_hash("# Notes\n"),
+            },
+        );
+
+        sync(
+            root,
+            &mut manifest,
+            vec![Output::region("CLAUDE.md", "# Notes")],
+            false,
+        );
+        let content = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert_eq!(content, region::apply(None, "# Notes").unwrap());
+        assert_eq!(manifest.files["CLAUDE.md"].kind, OutputKind::Region);
+
+        // Hand-written notes added afterwards are kept as usual.
+        fs::write(root.join("CLAUDE.md"), format!("Local\n{}", content)).unwrap();
+        sync(
+            root,
+            &mut manifest,
+            vec![Output::region("CLAUDE.md", "Gen")],
+            false,
+        );
+        let content = fs::read_to_string(root.join("CLAUDE.md")).unwrap();
+        assert!(content.starts_with("Local\n"));
+        assert!(content.contains("Gen"));
+    }
+}
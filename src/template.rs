@@ -0,0 +1,97 @@
+//! Minimal `{{ variable }}` templates for generated documents.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+/// Replaces every `{{ name }}` placeholder with its value. Unknown names are
+/// an error so that typos in user templates do not silently produce empty
+/// sections.
+pub fn render(template: &str, variables: &BTreeMap<String, String>) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            anyhow::bail!("Unclosed template placeholder: '{{{{{}'", after_open);
+        };
+
+        let name = after_open[..end].trim();
+        let value = variables.get(name).ok_or_else(|| {
+            let known: Vec<_> = variables.keys().map(String::as_str).collect();
+            anyhow::anyhow!(
+                "Unknown template variable '{}'. Available variables: {}",
+                name,
+                known.join(", ")
+            )
+        })?;
+        output.push_str(value);
+        rest = &after_open[end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Converts a heading into the anchor GitHub generates for it.
+pub fn heading_anchor(heading: &str) -> String {
+    heading
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_replaces_placeholders() {
+        let variables = vars(&[("project_name", "dozo"), ("rules", "R")]);
+        let output = render("# {{project_name}}\n{{ rules }}\n{{rules}}", &variables).unwrap();
+        assert_eq!(output, "# dozo\nR\nR");
+    }
+
+    #[test]
+    fn test_render_without_placeholders() {
+        assert_eq!(render("plain", &BTreeMap::new()).unwrap(), "plain");
+    }
+
+    #[test]
+    fn test_render_unknown_variable() {
+        let err = render("{{ projct }}", &vars(&[("project_name", "x")])).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown template variable 'projct'"));
+        assert!(err.to_string().contains("project_name"));
+    }
+
+    #[test]
+    fn test_render_unclosed_placeholder() {
+        assert!(render("{{ rules", &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_heading_anchor() {
+        assert_eq!(
+            heading_anchor("Frontend / React Rules"),
+            "frontend--react-rules"
+        );
+        assert_eq!(heading_anchor("API (v2)"), "api-v2");
+        assert_eq!(heading_anchor("セキュリティ"), "セキュリティ");
+    }
+}